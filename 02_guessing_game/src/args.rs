@@ -0,0 +1,90 @@
+use guessing_game::{Difficulty, DifficultyError};
+
+use crate::messages::Lang;
+
+pub struct Options {
+    pub difficulty: Option<Difficulty>,
+    pub seed: Option<u64>,
+    pub hints: bool,
+    pub players: Option<u32>,
+    pub reverse: bool,
+    pub show_scores: bool,
+}
+
+#[derive(Debug)]
+pub enum ArgError {
+    MissingValue(String),
+    BadNumber { name: String, value: String },
+    UnknownArgument(String),
+    UnknownLang(String),
+    NoPlayers,
+    CustomOnly,
+    Difficulty(DifficultyError),
+}
+
+// --lang 在这里只做校验，真正选语言的是 Lang::detect
+pub fn parse_args(args: impl Iterator<Item = String>) -> Result<Options, ArgError> {
+    let mut show_scores = false;
+    let mut hints = false;
+    let mut players = None;
+    let mut reverse = false;
+    let mut seed = None;
+    let mut level = None;
+    let mut min = None;
+    let mut max = None;
+    let mut attempts = None;
+
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next().ok_or_else(|| ArgError::MissingValue(name.to_string()))
+        };
+        match arg.as_str() {
+            "-d" | "--difficulty" => level = Some(value(&arg)?.trim().to_lowercase()),
+            "--min" => min = Some(parse_number(&arg, &value(&arg)?)?),
+            "--max" => max = Some(parse_number(&arg, &value(&arg)?)?),
+            "--attempts" => attempts = Some(parse_number(&arg, &value(&arg)?)?),
+            "--seed" => {
+                let text = value(&arg)?;
+                seed = Some(text.trim().parse().map_err(|_| bad_number(&arg, &text))?);
+            }
+            "--hints" => hints = true,
+            "--players" => {
+                let count = parse_number(&arg, &value(&arg)?)?;
+                if count == 0 {
+                    return Err(ArgError::NoPlayers);
+                }
+                players = Some(count);
+            }
+            "--reverse" => reverse = true,
+            "--scores" => show_scores = true,
+            "--lang" => {
+                let tag = value(&arg)?;
+                if Lang::from_tag(&tag).is_none() {
+                    return Err(ArgError::UnknownLang(tag));
+                }
+            }
+            other => return Err(ArgError::UnknownArgument(other.to_string())),
+        }
+    }
+
+    let difficulty = match (level.as_deref(), min, max, attempts) {
+        (None, None, None, None) => Ok(None),
+        (Some("custom"), Some(min), Some(max), Some(attempts))
+        | (None, Some(min), Some(max), Some(attempts)) => Difficulty::custom(min, max, attempts).map(Some),
+        (Some(level), None, None, None) => level.parse().map(Some),
+        (Some("custom"), _, _, _) | (None, _, _, _) => Err(DifficultyError::MissingCustomValues),
+        (Some(_), _, _, _) => return Err(ArgError::CustomOnly),
+    }
+    .map_err(ArgError::Difficulty)?;
+
+    Ok(Options { difficulty, seed, hints, players, reverse, show_scores })
+}
+
+fn parse_number(name: &str, value: &str) -> Result<u32, ArgError> {
+    value.trim().parse().map_err(|_| bad_number(name, value))
+}
+
+fn bad_number(name: &str, value: &str) -> ArgError {
+    ArgError::BadNumber { name: name.to_string(), value: value.to_string() }
+}
@@ -0,0 +1,97 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+// 难度预设：决定秘密数字的范围和最多可以猜几次
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    Custom { min: u32, max: u32, max_attempts: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DifficultyError {
+    EmptyRange { min: u32, max: u32 },
+    NoAttempts,
+    MissingCustomValues,
+    Unknown(String),
+}
+
+impl Difficulty {
+    pub fn custom(min: u32, max: u32, max_attempts: u32) -> Result<Difficulty, DifficultyError> {
+        if min >= max {
+            return Err(DifficultyError::EmptyRange { min, max });
+        }
+        if max_attempts == 0 {
+            return Err(DifficultyError::NoAttempts);
+        }
+        Ok(Difficulty::Custom { min, max, max_attempts })
+    }
+
+    pub fn range(&self) -> RangeInclusive<u32> {
+        match self {
+            Difficulty::Easy => 1..=50,
+            Difficulty::Normal => 1..=100,
+            Difficulty::Hard => 1..=1000,
+            Difficulty::Custom { min, max, .. } => *min..=*max,
+        }
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 10,
+            Difficulty::Normal => 7,
+            Difficulty::Hard => 10,
+            Difficulty::Custom { max_attempts, .. } => *max_attempts,
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Custom { .. } => "custom",
+        };
+        f.pad(name)
+    }
+}
+
+// 只解析预设难度，custom 需要额外提供范围和次数
+impl FromStr for Difficulty {
+    type Err = DifficultyError;
+
+    fn from_str(s: &str) -> Result<Difficulty, DifficultyError> {
+        match s.trim().to_lowercase().as_str() {
+            "easy" | "1" => Ok(Difficulty::Easy),
+            "normal" | "2" => Ok(Difficulty::Normal),
+            "hard" | "3" => Ok(Difficulty::Hard),
+            "custom" | "4" => Err(DifficultyError::MissingCustomValues),
+            other => Err(DifficultyError::Unknown(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for DifficultyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DifficultyError::EmptyRange { min, max } => {
+                write!(f, "The range {min}..={max} must contain at least two numbers.")
+            }
+            DifficultyError::NoAttempts => write!(f, "The number of attempts must be at least 1."),
+            DifficultyError::MissingCustomValues => {
+                write!(f, "A custom difficulty needs --min, --max and --attempts.")
+            }
+            DifficultyError::Unknown(name) => {
+                write!(f, "Unknown difficulty '{name}', expected easy, normal, hard or custom.")
+            }
+        }
+    }
+}
+
+impl Error for DifficultyError {}
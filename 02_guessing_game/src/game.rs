@@ -0,0 +1,202 @@
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+use crate::difficulty::Difficulty;
+use crate::guess::{Guess, GuessError};
+use crate::secret::NumberSource;
+
+// 一次猜测的结果，只有 TooSmall、TooBig 和 Win 会消耗次数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    TooSmall,
+    TooBig,
+    Win,
+    Invalid(GuessError),
+    OutOfRange(GuessError),
+    GameOver,
+}
+
+// 一局游戏的状态，不涉及任何终端输入输出
+#[derive(Debug, Clone)]
+pub struct GameSession {
+    difficulty: Difficulty,
+    secret_number: u32,
+    attempts: u32,
+    won: bool,
+    last_guess: Option<u32>,
+    low: u32,
+    high: u32,
+}
+
+impl GameSession {
+    pub fn new(difficulty: Difficulty, numbers: &mut dyn NumberSource) -> GameSession {
+        let secret_number = numbers.pick(difficulty.range());
+        GameSession::with_secret(difficulty, secret_number)
+    }
+
+    pub fn with_secret(difficulty: Difficulty, secret_number: u32) -> GameSession {
+        GameSession {
+            difficulty,
+            secret_number,
+            attempts: 0,
+            won: false,
+            last_guess: None,
+            low: *difficulty.range().start(),
+            high: *difficulty.range().end(),
+        }
+    }
+
+    // 游戏结束后再提交的猜测不会被计数，一律返回 GameOver
+    pub fn submit_guess(&mut self, input: &str) -> Outcome {
+        if self.is_over() {
+            return Outcome::GameOver;
+        }
+
+        let guess = match Guess::parse(input, &self.range()) {
+            Ok(guess) => guess,
+            Err(error @ GuessError::OutOfRange { .. }) => return Outcome::OutOfRange(error),
+            Err(error) => return Outcome::Invalid(error),
+        };
+
+        let value = guess.value();
+        self.attempts += 1;
+        self.last_guess = Some(value);
+        match value.cmp(&self.secret_number) {
+            Ordering::Less => {
+                self.low = self.low.max(value + 1);
+                Outcome::TooSmall
+            }
+            Ordering::Greater => {
+                self.high = self.high.min(value - 1);
+                Outcome::TooBig
+            }
+            Ordering::Equal => {
+                self.won = true;
+                Outcome::Win
+            }
+        }
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    pub fn range(&self) -> RangeInclusive<u32> {
+        self.difficulty.range()
+    }
+
+    // 根据之前的 TooSmall/TooBig 缩小后的区间，秘密数字一定在里面
+    pub fn possible_range(&self) -> RangeInclusive<u32> {
+        self.low..=self.high
+    }
+
+    pub fn last_guess(&self) -> Option<u32> {
+        self.last_guess
+    }
+
+    pub fn secret_number(&self) -> u32 {
+        self.secret_number
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn attempts_left(&self) -> u32 {
+        self.difficulty.max_attempts().saturating_sub(self.attempts)
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.won
+    }
+
+    pub fn is_lost(&self) -> bool {
+        !self.won && self.attempts_left() == 0
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.is_won() || self.is_lost()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcomes_follow_the_secret_number() {
+        let mut session = GameSession::with_secret(Difficulty::Normal, 42);
+        assert_eq!(session.submit_guess("10"), Outcome::TooSmall);
+        assert_eq!(session.submit_guess("90"), Outcome::TooBig);
+        assert_eq!(session.submit_guess("42"), Outcome::Win);
+        assert_eq!(session.attempts(), 3);
+        assert_eq!(session.last_guess(), Some(42));
+        assert!(session.is_won());
+        assert!(!session.is_lost());
+    }
+
+    #[test]
+    fn invalid_guesses_do_not_count() {
+        let mut session = GameSession::with_secret(Difficulty::Normal, 42);
+        assert_eq!(session.submit_guess(""), Outcome::Invalid(GuessError::Empty));
+        assert!(matches!(session.submit_guess("abc"), Outcome::Invalid(GuessError::NotANumber(_))));
+        assert!(matches!(session.submit_guess("101"), Outcome::OutOfRange(GuessError::OutOfRange { .. })));
+        assert_eq!(session.attempts(), 0);
+        assert_eq!(session.last_guess(), None);
+    }
+
+    #[test]
+    fn running_out_of_attempts_loses() {
+        let difficulty = Difficulty::custom(1, 10, 2).unwrap();
+        let mut session = GameSession::with_secret(difficulty, 7);
+        assert_eq!(session.submit_guess("1"), Outcome::TooSmall);
+        assert_eq!(session.attempts_left(), 1);
+        assert!(!session.is_over());
+        assert_eq!(session.submit_guess("2"), Outcome::TooSmall);
+        assert_eq!(session.attempts_left(), 0);
+        assert!(session.is_lost());
+        assert!(session.is_over());
+    }
+
+    #[test]
+    fn guesses_after_the_game_is_over_are_rejected() {
+        let difficulty = Difficulty::custom(1, 10, 1).unwrap();
+        let mut lost = GameSession::with_secret(difficulty, 7);
+        lost.submit_guess("3");
+        assert_eq!(lost.submit_guess("7"), Outcome::GameOver);
+        assert_eq!(lost.attempts(), 1);
+        assert!(!lost.is_won());
+
+        let mut won = GameSession::with_secret(Difficulty::Easy, 5);
+        won.submit_guess("5");
+        assert_eq!(won.submit_guess("5"), Outcome::GameOver);
+        assert_eq!(won.attempts(), 1);
+    }
+
+    #[test]
+    fn possible_range_narrows_around_the_secret() {
+        let mut session = GameSession::with_secret(Difficulty::Normal, 42);
+        assert_eq!(session.possible_range(), 1..=100);
+        session.submit_guess("30");
+        assert_eq!(session.possible_range(), 31..=100);
+        session.submit_guess("60");
+        assert_eq!(session.possible_range(), 31..=59);
+        // 已经排除过的数字不会让区间变宽
+        session.submit_guess("10");
+        session.submit_guess("80");
+        assert_eq!(session.possible_range(), 31..=59);
+        session.submit_guess("42");
+        assert_eq!(session.possible_range(), 31..=59);
+    }
+
+    #[test]
+    fn possible_range_handles_the_edges_of_the_range() {
+        let mut low = GameSession::with_secret(Difficulty::Easy, 1);
+        low.submit_guess("2");
+        assert_eq!(low.possible_range(), 1..=1);
+
+        let mut high = GameSession::with_secret(Difficulty::Easy, 50);
+        high.submit_guess("49");
+        assert_eq!(high.possible_range(), 50..=50);
+    }
+}
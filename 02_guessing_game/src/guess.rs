@@ -0,0 +1,109 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+// 和 n09_result 里的 Guess 一样只能保存合法的值，但用 Result 代替 panic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Guess {
+    value: u32,
+}
+
+// OutOfRange 的 value 保存原始文字，这样超出 i64 的一长串数字也能原样报告
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError {
+    Empty,
+    NotANumber(String),
+    OutOfRange { value: String, min: u32, max: u32 },
+}
+
+impl Guess {
+    pub fn try_new(value: i64, range: &RangeInclusive<u32>) -> Result<Guess, GuessError> {
+        match u32::try_from(value) {
+            Ok(value) if range.contains(&value) => Ok(Guess { value }),
+            _ => Err(GuessError::OutOfRange {
+                value: value.to_string(),
+                min: *range.start(),
+                max: *range.end(),
+            }),
+        }
+    }
+
+    pub fn parse(input: &str, range: &RangeInclusive<u32>) -> Result<Guess, GuessError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(GuessError::Empty);
+        }
+        match input.parse() {
+            Ok(value) => Guess::try_new(value, range),
+            // 纯数字但超出 i64 的输入也算超出范围，而不是“不是数字”
+            Err(_) if is_integer(input) => Err(GuessError::OutOfRange {
+                value: input.to_string(),
+                min: *range.start(),
+                max: *range.end(),
+            }),
+            Err(_) => Err(GuessError::NotANumber(input.to_string())),
+        }
+    }
+
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+fn is_integer(text: &str) -> bool {
+    let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuessError::Empty => write!(f, "Please type a number."),
+            GuessError::NotANumber(input) => write!(f, "'{input}' is not a number."),
+            GuessError::OutOfRange { value, min, max } => {
+                write!(f, "{value} is out of range, the secret number will be between {min} and {max}.")
+            }
+        }
+    }
+}
+
+impl Error for GuessError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn out_of_range(value: &str) -> Result<Guess, GuessError> {
+        Err(GuessError::OutOfRange { value: value.to_string(), min: 1, max: 100 })
+    }
+
+    #[test]
+    fn parses_numbers_inside_the_range() {
+        assert_eq!(Guess::parse(" 42\n", &(1..=100)).map(|guess| guess.value()), Ok(42));
+        assert_eq!(Guess::parse("1", &(1..=100)).map(|guess| guess.value()), Ok(1));
+        assert_eq!(Guess::parse("+100", &(1..=100)).map(|guess| guess.value()), Ok(100));
+    }
+
+    #[test]
+    fn rejects_empty_and_non_numeric_input() {
+        assert_eq!(Guess::parse("  ", &(1..=100)), Err(GuessError::Empty));
+        assert_eq!(Guess::parse("abc", &(1..=100)), Err(GuessError::NotANumber(String::from("abc"))));
+        assert_eq!(Guess::parse("4.5", &(1..=100)), Err(GuessError::NotANumber(String::from("4.5"))));
+        assert_eq!(Guess::parse("-", &(1..=100)), Err(GuessError::NotANumber(String::from("-"))));
+        assert_eq!(Guess::parse("12a", &(1..=100)), Err(GuessError::NotANumber(String::from("12a"))));
+    }
+
+    #[test]
+    fn numbers_outside_the_range_are_out_of_range() {
+        assert_eq!(Guess::parse("0", &(1..=100)), out_of_range("0"));
+        assert_eq!(Guess::parse("101", &(1..=100)), out_of_range("101"));
+        assert_eq!(Guess::parse("-5", &(1..=100)), out_of_range("-5"));
+        assert_eq!(Guess::parse("4294967296", &(1..=100)), out_of_range("4294967296"));
+    }
+
+    #[test]
+    fn numbers_too_large_for_i64_are_out_of_range() {
+        assert_eq!(Guess::parse("99999999999999999999", &(1..=100)), out_of_range("99999999999999999999"));
+        assert_eq!(Guess::parse("-99999999999999999999", &(1..=100)), out_of_range("-99999999999999999999"));
+    }
+}
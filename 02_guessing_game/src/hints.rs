@@ -0,0 +1,37 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+// 提示模式下根据猜测和秘密数字的距离给出冷热提示
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Proximity {
+    Hot,
+    Warm,
+    Cold,
+}
+
+impl Proximity {
+    // 距离阈值按范围大小计算：5% 以内算热，20% 以内算温
+    pub fn of(guess: u32, secret_number: u32, range: &RangeInclusive<u32>) -> Proximity {
+        let span = u64::from(*range.end()) - u64::from(*range.start()) + 1;
+        let distance = u64::from(guess.abs_diff(secret_number));
+
+        if distance <= (span / 20).max(1) {
+            Proximity::Hot
+        } else if distance <= (span / 5).max(2) {
+            Proximity::Warm
+        } else {
+            Proximity::Cold
+        }
+    }
+}
+
+impl fmt::Display for Proximity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Proximity::Hot => "Hot! You're very close.",
+            Proximity::Warm => "Warm, getting closer.",
+            Proximity::Cold => "Cold, still far away.",
+        };
+        f.pad(text)
+    }
+}
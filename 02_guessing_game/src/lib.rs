@@ -0,0 +1,12 @@
+pub mod difficulty;
+pub mod game;
+pub mod guess;
+pub mod hints;
+pub mod multiplayer;
+pub mod reverse;
+pub mod scores;
+pub mod secret;
+
+pub use crate::difficulty::{Difficulty, DifficultyError};
+pub use crate::game::{GameSession, Outcome};
+pub use crate::guess::{Guess, GuessError};
@@ -1,35 +1,363 @@
+use std::env;
 use std::io;
-use rand::Rng;
-use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::process;
+use std::time::{Duration, Instant};
+
+use guessing_game::hints::Proximity;
+use guessing_game::multiplayer::HotSeat;
+use guessing_game::reverse::{self, ComputerGuesser, Reply};
+use guessing_game::scores::{self, Score};
+use guessing_game::secret::{self, NumberSource};
+use guessing_game::{Difficulty, GameSession, Outcome};
+
+use crate::args::parse_args;
+use crate::messages::{Lang, Message};
+
+mod args;
+mod messages;
+
+const SCORES_FILE: &str = "guessing_game_scores.txt";
 
 fn main() {
-    println!("Guess the number!");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let lang = Lang::detect(&args);
 
-    let secret_number = rand::thread_rng().gen_range(1..=100);
-    // println!("The secret number is: {secret_number}");
+    let options = match parse_args(args.into_iter()) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}", Message::Arg(&error).text(lang));
+            eprintln!("{}", Message::Usage.text(lang));
+            process::exit(2);
+        }
+    };
 
-    loop {
+    if options.show_scores {
+        show_scores(lang);
+        return;
+    }
+
+    let difficulty = match options.difficulty {
+        Some(difficulty) => difficulty,
+        None => choose_difficulty(lang),
+    };
 
-        println!("Please input your guess.");
+    if options.reverse {
+        play_reverse(lang, difficulty);
+        return;
+    }
+
+    if let Some(players) = options.players {
+        play_hot_seat(lang, difficulty, secret::source(options.seed).as_mut(), players, options.hints);
+        return;
+    }
 
-        let mut guess = String::new();
+    play(lang, difficulty, secret::source(options.seed).as_mut(), options.hints);
+}
 
-        io::stdin().read_line(&mut guess)
-            .expect("Failed to read line");
+fn say(lang: Lang, message: Message) {
+    println!("{}", message.text(lang));
+}
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
+fn play(lang: Lang, difficulty: Difficulty, numbers: &mut dyn NumberSource, hints: bool) {
+    say(lang, Message::Title);
+
+    let mut session = GameSession::new(difficulty, numbers);
+    // println!("The secret number is: {}", session.secret_number());
+
+    say(lang, Message::Intro { difficulty, players: false });
+
+    let started = Instant::now();
+
+    loop {
+
+        say(lang, Message::InputGuess);
+
+        let guess = match read_line() {
+            Some(line) => line,
+            None => return,
         };
 
-        println!("You guessed: {}", guess);
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
+        let possible = session.possible_range();
+        match session.submit_guess(&guess) {
+            Outcome::Invalid(error) | Outcome::OutOfRange(error) => {
+                say(lang, Message::Guess(&error));
+                continue;
+            }
+            Outcome::GameOver => break,
+            Outcome::TooSmall => {
+                say(lang, Message::YouGuessed(&guess));
+                say(lang, Message::TooSmall);
+            }
+            Outcome::TooBig => {
+                say(lang, Message::YouGuessed(&guess));
+                say(lang, Message::TooBig);
+            }
+            Outcome::Win => {
+                say(lang, Message::YouGuessed(&guess));
+                say(lang, Message::YouWin);
+                let elapsed = started.elapsed();
+                say(lang, Message::WinStats { attempts: session.attempts(), seconds: elapsed.as_secs_f64() });
+                say(lang, Message::AskName);
+                let player = read_line().unwrap_or_default();
+                save_score(lang, player.trim(), difficulty, session.attempts(), elapsed);
                 break;
+            }
+        }
+
+        if hints {
+            print_hint(lang, &session, &possible);
+        }
+
+        if session.is_lost() {
+            say(lang, Message::Lose { attempts: session.attempts(), secret_number: session.secret_number() });
+            break;
+        }
+        say(lang, Message::AttemptsLeft(session.attempts_left()));
+    }
+}
+
+// possible 是这次猜测之前已经缩小到的区间
+fn print_hint(lang: Lang, session: &GameSession, possible: &RangeInclusive<u32>) {
+    let guess = match session.last_guess() {
+        Some(guess) => guess,
+        None => return,
+    };
+
+    if !possible.contains(&guess) {
+        say(lang, Message::RuledOut { low: *possible.start(), high: *possible.end(), guess });
+    }
+    say(lang, Message::Proximity(Proximity::of(guess, session.secret_number(), &session.range())));
+
+    if !session.is_over() {
+        let narrowed = session.possible_range();
+        say(lang, Message::Narrowed { low: *narrowed.start(), high: *narrowed.end() });
+    }
+}
+
+fn play_hot_seat(lang: Lang, difficulty: Difficulty, numbers: &mut dyn NumberSource, players: u32, hints: bool) {
+    let mut names = Vec::new();
+    for number in 1..=players {
+        say(lang, Message::AskPlayerName(number));
+        let name = read_line().unwrap_or_default();
+        names.push(match name.trim() {
+            "" => Message::DefaultPlayerName(number).text(lang),
+            name => name.to_string(),
+        });
+    }
+
+    let mut game = HotSeat::new(names, difficulty, numbers);
+    say(lang, Message::Title);
+    say(lang, Message::Intro { difficulty, players: true });
+
+    let started = Instant::now();
+
+    while !game.is_over() {
+        let index = game.current_index();
+        let name = game.current_player().name.clone();
+        say(lang, Message::PlayerInputGuess(&name));
+
+        let guess = match read_line() {
+            Some(line) => line,
+            None => return,
+        };
+
+        let possible = game.current_player().session.possible_range();
+        let outcome = game.submit_guess(&guess);
+        match &outcome {
+            Outcome::Invalid(error) | Outcome::OutOfRange(error) => {
+                say(lang, Message::Guess(error));
+                continue;
+            }
+            Outcome::GameOver => break,
+            _ => {}
+        }
+
+        say(lang, Message::PlayerGuessed { name: &name, guess: &guess });
+        match outcome {
+            Outcome::TooSmall => say(lang, Message::TooSmall),
+            Outcome::TooBig => say(lang, Message::TooBig),
+            _ => say(lang, Message::PlayerWins(&name)),
+        }
+
+        let session = &game.players()[index].session;
+        if hints && !session.is_won() {
+            print_hint(lang, session, &possible);
+        }
+    }
+
+    match game.winner() {
+        Some(winner) => {
+            let attempts = winner.session.attempts();
+            save_score(lang, &winner.name, difficulty, attempts, started.elapsed());
+        }
+        None => say(lang, Message::NobodyWon(game.secret_number())),
+    }
+
+    say(lang, Message::SummaryHeader);
+    for player in game.players() {
+        let result = if player.session.is_won() {
+            Message::ResultWinner.text(lang)
+        } else if player.session.is_lost() {
+            Message::ResultOut.text(lang)
+        } else {
+            Message::ResultUnfinished.text(lang)
+        };
+        println!("{:<20} {:>8} {result}", player.name, player.session.attempts());
+    }
+}
+
+fn play_reverse(lang: Lang, difficulty: Difficulty) {
+    let range = difficulty.range();
+    say(lang, Message::ReverseIntro { low: *range.start(), high: *range.end() });
+    say(lang, Message::ReverseHowTo);
+
+    let mut guesser = ComputerGuesser::new(range.clone());
+    while !guesser.is_found() {
+        say(lang, Message::ComputerGuess(guesser.current_guess()));
+
+        let reply = match read_line() {
+            Some(line) => line,
+            None => return,
+        };
+        let reply: Reply = match reply.parse() {
+            Ok(reply) => reply,
+            Err(error) => {
+                say(lang, Message::BadReply(&error));
+                continue;
+            }
+        };
+
+        if let Err(cheating) = guesser.reply(reply) {
+            say(lang, Message::Cheating(&cheating));
+            return;
+        }
+    }
+
+    say(
+        lang,
+        Message::ComputerWins {
+            number: guesser.current_guess(),
+            guesses: guesser.guesses(),
+            bound: reverse::max_guesses(&range),
+            count: u64::from(*range.end()) - u64::from(*range.start()) + 1,
+        },
+    );
+}
+
+fn choose_difficulty(lang: Lang) -> Difficulty {
+    loop {
+        say(lang, Message::ChooseDifficulty);
+        for (key, difficulty) in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard].into_iter().enumerate() {
+            say(lang, Message::MenuPreset { key: key + 1, difficulty });
+        }
+        say(lang, Message::MenuCustom);
+
+        let choice = match read_line() {
+            Some(line) => line,
+            None => process::exit(0),
+        };
+
+        match choice.trim().to_lowercase().as_str() {
+            "4" | "custom" => {
+                if let Some(difficulty) = read_custom_difficulty(lang) {
+                    return difficulty;
+                }
+            }
+            other => match other.parse() {
+                Ok(difficulty) => return difficulty,
+                Err(error) => say(lang, Message::Difficulty(&error)),
             },
         }
     }
-}
\ No newline at end of file
+}
+
+fn read_custom_difficulty(lang: Lang) -> Option<Difficulty> {
+    let min = ask_number(lang, &Message::AskMin)?;
+    let max = ask_number(lang, &Message::AskMax)?;
+    let attempts = ask_number(lang, &Message::AskAttempts)?;
+    match Difficulty::custom(min, max, attempts) {
+        Ok(difficulty) => Some(difficulty),
+        Err(error) => {
+            say(lang, Message::Difficulty(&error));
+            None
+        }
+    }
+}
+
+fn ask_number(lang: Lang, prompt: &Message) -> Option<u32> {
+    loop {
+        println!("{}", prompt.text(lang));
+        let line = read_line()?;
+        match line.trim().parse() {
+            Ok(number) => return Some(number),
+            Err(_) => say(lang, Message::BadNumber(&line)),
+        }
+    }
+}
+
+fn show_scores(lang: Lang) {
+    let path = Path::new(SCORES_FILE);
+    let mut file = match scores::load(path) {
+        Ok(file) => file,
+        Err(error) => {
+            eprintln!("{}", Message::ReadScoresFailed { path, error: &error }.text(lang));
+            process::exit(1);
+        }
+    };
+
+    for line in &file.corrupt_lines {
+        eprintln!("{}", Message::CorruptLine { line: *line, path }.text(lang));
+    }
+    if file.scores.is_empty() {
+        say(lang, Message::NoScores);
+        return;
+    }
+
+    scores::sort_leaderboard(&mut file.scores);
+    say(lang, Message::ScoresHeader);
+    // 名次在每个难度（以及每种自定义设置）里单独计算
+    let mut rank = 0;
+    for (index, score) in file.scores.iter().enumerate() {
+        let same_group = index > 0 && file.scores[index - 1].difficulty == score.difficulty;
+        rank = if same_group { rank + 1 } else { 1 };
+        let level = match score.difficulty.parse() {
+            Ok(difficulty) => messages::difficulty_name(difficulty, lang),
+            Err(_) => score.difficulty.as_str(),
+        };
+        println!(
+            "{:<4} {:<20} {:<20} {:>8} {:>9.1}s",
+            rank,
+            score.player,
+            level,
+            score.attempts,
+            score.elapsed.as_secs_f64()
+        );
+    }
+}
+
+fn save_score(lang: Lang, player: &str, difficulty: Difficulty, attempts: u32, elapsed: Duration) {
+    let player = match player {
+        "" => Message::AnonymousPlayer.text(lang),
+        name => name.to_string(),
+    };
+
+    let path = Path::new(SCORES_FILE);
+    let score = Score { player, difficulty: scores::difficulty_key(difficulty), attempts, elapsed };
+    if let Err(error) = scores::record(path, &score) {
+        eprintln!("{}", Message::SaveScoreFailed { path, error: &error }.text(lang));
+    }
+}
+
+// 读到文件末尾（EOF）时返回 None
+fn read_line() -> Option<String> {
+    let mut line = String::new();
+    let bytes = io::stdin().read_line(&mut line)
+        .expect("Failed to read line");
+    if bytes == 0 {
+        None
+    } else {
+        Some(line)
+    }
+}
@@ -0,0 +1,290 @@
+use std::io;
+use std::path::Path;
+
+use guessing_game::hints::Proximity;
+use guessing_game::reverse::{Cheating, ParseReplyError, Reply};
+use guessing_game::{Difficulty, DifficultyError, GuessError};
+
+use crate::args::ArgError;
+
+pub use lang::Lang;
+
+// 所有面向用户的文字都在这里，按语言分别给出
+pub enum Message<'a> {
+    Usage,
+    Arg(&'a ArgError),
+    Title,
+    Intro { difficulty: Difficulty, players: bool },
+    ChooseDifficulty,
+    MenuPreset { key: usize, difficulty: Difficulty },
+    MenuCustom,
+    AskMin,
+    AskMax,
+    AskAttempts,
+    BadNumber(&'a str),
+    Difficulty(&'a DifficultyError),
+    InputGuess,
+    PlayerInputGuess(&'a str),
+    YouGuessed(&'a str),
+    PlayerGuessed { name: &'a str, guess: &'a str },
+    Guess(&'a GuessError),
+    TooSmall,
+    TooBig,
+    YouWin,
+    PlayerWins(&'a str),
+    WinStats { attempts: u32, seconds: f64 },
+    AskName,
+    AnonymousPlayer,
+    Lose { attempts: u32, secret_number: u32 },
+    AttemptsLeft(u32),
+    RuledOut { low: u32, high: u32, guess: u32 },
+    Proximity(Proximity),
+    Narrowed { low: u32, high: u32 },
+    AskPlayerName(u32),
+    DefaultPlayerName(u32),
+    NobodyWon(u32),
+    SummaryHeader,
+    ResultWinner,
+    ResultOut,
+    ResultUnfinished,
+    ReverseIntro { low: u32, high: u32 },
+    ReverseHowTo,
+    ComputerGuess(u32),
+    BadReply(&'a ParseReplyError),
+    Cheating(&'a Cheating),
+    ComputerWins { number: u32, guesses: u32, bound: u32, count: u64 },
+    NoScores,
+    ScoresHeader,
+    CorruptLine { line: usize, path: &'a Path },
+    ReadScoresFailed { path: &'a Path, error: &'a io::Error },
+    SaveScoreFailed { path: &'a Path, error: &'a io::Error },
+}
+
+impl Message<'_> {
+    pub fn text(&self, lang: Lang) -> String {
+        match lang {
+            Lang::En => self.english(),
+            Lang::Zh => self.chinese(),
+        }
+    }
+
+    // 库里的类型自带英文的 Display，英文界面直接复用，不再另写一份
+    fn english(&self) -> String {
+        match self {
+            Message::Usage => String::from(
+                "Usage: guessing_game [--difficulty easy|normal|hard|custom] [--min N --max N --attempts N] \
+                 [--seed N] [--hints] [--players N] [--reverse] [--scores] [--lang en|zh]",
+            ),
+            Message::Arg(error) => match error {
+                ArgError::MissingValue(name) => format!("Missing value for {name}."),
+                ArgError::BadNumber { name, value } => format!("{name} expects a positive number, got '{value}'."),
+                ArgError::UnknownArgument(arg) => format!("Unknown argument '{arg}'."),
+                ArgError::UnknownLang(tag) => format!("Unknown language '{tag}', expected en or zh."),
+                ArgError::NoPlayers => String::from("--players needs at least one player."),
+                ArgError::CustomOnly => {
+                    String::from("--min, --max and --attempts only apply to the custom difficulty.")
+                }
+                ArgError::Difficulty(error) => Message::Difficulty(error).english(),
+            },
+            Message::Title => String::from("Guess the number!"),
+            Message::Intro { difficulty, players } => format!(
+                "Difficulty: {}. The secret number is between {} and {}, {} {} attempts.",
+                difficulty_name(*difficulty, Lang::En),
+                difficulty.range().start(),
+                difficulty.range().end(),
+                if *players { "each player has" } else { "you have" },
+                difficulty.max_attempts()
+            ),
+            Message::ChooseDifficulty => String::from("Choose a difficulty:"),
+            Message::MenuPreset { key, difficulty } => format!(
+                "  {key}) {:<6} {}-{}, {} attempts",
+                difficulty_name(*difficulty, Lang::En),
+                difficulty.range().start(),
+                difficulty.range().end(),
+                difficulty.max_attempts()
+            ),
+            Message::MenuCustom => String::from("  4) custom"),
+            Message::AskMin => String::from("Lowest number:"),
+            Message::AskMax => String::from("Highest number:"),
+            Message::AskAttempts => String::from("Number of attempts:"),
+            Message::BadNumber(value) => format!("'{}' is not a positive number.", value.trim()),
+            Message::Difficulty(error) => error.to_string(),
+            Message::InputGuess => String::from("Please input your guess."),
+            Message::PlayerInputGuess(name) => format!("{name}, please input your guess."),
+            Message::YouGuessed(guess) => format!("You guessed: {}", guess.trim()),
+            Message::PlayerGuessed { name, guess } => format!("{name} guessed: {}", guess.trim()),
+            Message::Guess(error) => error.to_string(),
+            Message::TooSmall => String::from("Too small!"),
+            Message::TooBig => String::from("Too big!"),
+            Message::YouWin => String::from("You win!"),
+            Message::PlayerWins(name) => format!("{name} wins!"),
+            Message::WinStats { attempts, seconds } => {
+                format!("You needed {attempts} attempts and {seconds:.1} seconds.")
+            }
+            Message::AskName => String::from("Enter your name for the high-score table:"),
+            Message::AnonymousPlayer => String::from("anonymous"),
+            Message::Lose { attempts, secret_number } => {
+                format!("You lose! You used all {attempts} attempts, the secret number was {secret_number}.")
+            }
+            Message::AttemptsLeft(left) => format!("{left} attempts left."),
+            Message::RuledOut { low, high, guess } => {
+                format!("Careful: you already knew the number is between {low} and {high}, {guess} was ruled out.")
+            }
+            Message::Proximity(proximity) => proximity.to_string(),
+            Message::Narrowed { low, high } => format!("The secret number is between {low} and {high}."),
+            Message::AskPlayerName(number) => format!("Name of player {number}:"),
+            Message::DefaultPlayerName(number) => format!("Player {number}"),
+            Message::NobodyWon(secret_number) => {
+                format!("Nobody found it, the secret number was {secret_number}.")
+            }
+            Message::SummaryHeader => format!("{:<20} {:>8} Result", "Player", "Attempts"),
+            Message::ResultWinner => String::from("winner"),
+            Message::ResultOut => String::from("out of attempts"),
+            Message::ResultUnfinished => String::from("unfinished"),
+            Message::ReverseIntro { low, high } => {
+                format!("Think of a number between {low} and {high}, I'll try to guess it.")
+            }
+            Message::ReverseHowTo => String::from("Answer each guess with higher, lower or correct."),
+            Message::ComputerGuess(guess) => format!("Is it {guess}?"),
+            Message::BadReply(error) => error.to_string(),
+            Message::Cheating(cheating) => cheating.to_string(),
+            Message::ComputerWins { number, guesses, bound, count } => format!(
+                "Got it! Your number is {number}. I needed {guesses} guesses, \
+                 binary search never needs more than {bound} for {count} numbers."
+            ),
+            Message::NoScores => String::from("No high scores yet."),
+            Message::ScoresHeader => {
+                format!("{:<4} {:<20} {:<20} {:>8} {:>10}", "#", "Player", "Level", "Attempts", "Time")
+            }
+            Message::CorruptLine { line, path } => {
+                format!("Skipping corrupt line {line} in {}.", path.display())
+            }
+            Message::ReadScoresFailed { path, error } => format!("Could not read {}: {error}", path.display()),
+            Message::SaveScoreFailed { path, error } => {
+                format!("Could not save your score to {}: {error}", path.display())
+            }
+        }
+    }
+
+    fn chinese(&self) -> String {
+        match self {
+            Message::Usage => String::from(
+                "用法: guessing_game [--difficulty easy|normal|hard|custom] [--min N --max N --attempts N] \
+                 [--seed N] [--hints] [--players N] [--reverse] [--scores] [--lang en|zh]",
+            ),
+            Message::Arg(error) => match error {
+                ArgError::MissingValue(name) => format!("{name} 缺少参数值。"),
+                ArgError::BadNumber { name, value } => format!("{name} 需要一个正整数，收到的是 '{value}'。"),
+                ArgError::UnknownArgument(arg) => format!("未知参数 '{arg}'。"),
+                ArgError::UnknownLang(tag) => format!("未知语言 '{tag}'，可选 en 或 zh。"),
+                ArgError::NoPlayers => String::from("--players 至少需要一名玩家。"),
+                ArgError::CustomOnly => String::from("--min、--max 和 --attempts 只能用于自定义难度。"),
+                ArgError::Difficulty(error) => Message::Difficulty(error).chinese(),
+            },
+            Message::Title => String::from("猜数字！"),
+            Message::Intro { difficulty, players } => format!(
+                "难度：{}。秘密数字在 {} 到 {} 之间，{}可以猜 {} 次。",
+                difficulty_name(*difficulty, Lang::Zh),
+                difficulty.range().start(),
+                difficulty.range().end(),
+                if *players { "每位玩家" } else { "你" },
+                difficulty.max_attempts()
+            ),
+            Message::ChooseDifficulty => String::from("请选择难度："),
+            Message::MenuPreset { key, difficulty } => format!(
+                "  {key}) {} {}-{}，{} 次机会",
+                difficulty_name(*difficulty, Lang::Zh),
+                difficulty.range().start(),
+                difficulty.range().end(),
+                difficulty.max_attempts()
+            ),
+            Message::MenuCustom => String::from("  4) 自定义"),
+            Message::AskMin => String::from("最小的数字："),
+            Message::AskMax => String::from("最大的数字："),
+            Message::AskAttempts => String::from("可以猜几次："),
+            Message::BadNumber(value) => format!("'{}' 不是一个正整数。", value.trim()),
+            Message::Difficulty(error) => match error {
+                DifficultyError::EmptyRange { min, max } => format!("范围 {min}..={max} 至少要包含两个数字。"),
+                DifficultyError::NoAttempts => String::from("至少要有 1 次机会。"),
+                DifficultyError::MissingCustomValues => {
+                    String::from("自定义难度需要同时提供 --min、--max 和 --attempts。")
+                }
+                DifficultyError::Unknown(name) => {
+                    format!("未知难度 '{name}'，可选 easy、normal、hard 或 custom。")
+                }
+            },
+            Message::InputGuess => String::from("请输入你的猜测。"),
+            Message::PlayerInputGuess(name) => format!("{name}，请输入你的猜测。"),
+            Message::YouGuessed(guess) => format!("你猜的是：{}", guess.trim()),
+            Message::PlayerGuessed { name, guess } => format!("{name} 猜的是：{}", guess.trim()),
+            Message::Guess(error) => match error {
+                GuessError::Empty => String::from("请输入一个数字。"),
+                GuessError::NotANumber(input) => format!("'{input}' 不是数字。"),
+                GuessError::OutOfRange { value, min, max } => {
+                    format!("{value} 超出范围了，秘密数字在 {min} 到 {max} 之间。")
+                }
+            },
+            Message::TooSmall => String::from("太小了！"),
+            Message::TooBig => String::from("太大了！"),
+            Message::YouWin => String::from("你赢了！"),
+            Message::PlayerWins(name) => format!("{name} 赢了！"),
+            Message::WinStats { attempts, seconds } => format!("你用了 {attempts} 次，耗时 {seconds:.1} 秒。"),
+            Message::AskName => String::from("请输入你的名字，记入排行榜："),
+            Message::AnonymousPlayer => String::from("匿名"),
+            Message::Lose { attempts, secret_number } => {
+                format!("你输了！{attempts} 次机会已经用完，秘密数字是 {secret_number}。")
+            }
+            Message::AttemptsLeft(left) => format!("还剩 {left} 次机会。"),
+            Message::RuledOut { low, high, guess } => {
+                format!("注意：之前已经知道数字在 {low} 到 {high} 之间，{guess} 早就被排除了。")
+            }
+            Message::Proximity(proximity) => match proximity {
+                Proximity::Hot => String::from("很热！已经非常接近了。"),
+                Proximity::Warm => String::from("温暖，越来越近了。"),
+                Proximity::Cold => String::from("很冷，还差得远。"),
+            },
+            Message::Narrowed { low, high } => format!("秘密数字在 {low} 到 {high} 之间。"),
+            Message::AskPlayerName(number) => format!("第 {number} 位玩家的名字："),
+            Message::DefaultPlayerName(number) => format!("玩家 {number}"),
+            Message::NobodyWon(secret_number) => format!("没有人猜中，秘密数字是 {secret_number}。"),
+            Message::SummaryHeader => format!("{:<20} {:>8} 结果", "玩家", "次数"),
+            Message::ResultWinner => String::from("获胜"),
+            Message::ResultOut => String::from("次数用完"),
+            Message::ResultUnfinished => String::from("未完成"),
+            Message::ReverseIntro { low, high } => {
+                format!("请在心里想一个 {low} 到 {high} 之间的数字，我来猜。")
+            }
+            Message::ReverseHowTo => String::from("每次请回答 大、小 或 对（也可以用 higher、lower、correct）。"),
+            Message::ComputerGuess(guess) => format!("是 {guess} 吗？"),
+            Message::BadReply(error) => format!("请回答 大、小 或 对，而不是 '{}'。", error.0),
+            Message::Cheating(cheating) => format!(
+                "你作弊了！根据你的回答只剩下 {} 到 {}，不可能比 {} 更{}。",
+                cheating.low,
+                cheating.high,
+                cheating.guess,
+                if cheating.reply == Reply::Higher { "大" } else { "小" }
+            ),
+            Message::ComputerWins { number, guesses, bound, count } => format!(
+                "猜到了！你想的数字是 {number}。我用了 {guesses} 次，{count} 个数字用二分查找最多只需要 {bound} 次。"
+            ),
+            Message::NoScores => String::from("还没有任何成绩。"),
+            Message::ScoresHeader => format!("{:<4} {:<20} {:<20} {:>8} {:>10}", "#", "玩家", "难度", "次数", "用时"),
+            Message::CorruptLine { line, path } => format!("跳过 {} 中损坏的第 {line} 行。", path.display()),
+            Message::ReadScoresFailed { path, error } => format!("无法读取 {}：{error}", path.display()),
+            Message::SaveScoreFailed { path, error } => format!("无法把成绩保存到 {}：{error}", path.display()),
+        }
+    }
+}
+
+pub fn difficulty_name(difficulty: Difficulty, lang: Lang) -> &'static str {
+    match (lang, difficulty) {
+        (Lang::En, Difficulty::Easy) => "easy",
+        (Lang::En, Difficulty::Normal) => "normal",
+        (Lang::En, Difficulty::Hard) => "hard",
+        (Lang::En, Difficulty::Custom { .. }) => "custom",
+        (Lang::Zh, Difficulty::Easy) => "简单",
+        (Lang::Zh, Difficulty::Normal) => "普通",
+        (Lang::Zh, Difficulty::Hard) => "困难",
+        (Lang::Zh, Difficulty::Custom { .. }) => "自定义",
+    }
+}
@@ -0,0 +1,83 @@
+use crate::difficulty::Difficulty;
+use crate::game::{GameSession, Outcome};
+use crate::secret::NumberSource;
+
+// 热座模式：多名玩家在同一个终端轮流猜同一个秘密数字，每人各自计数
+#[derive(Debug, Clone)]
+pub struct Player {
+    pub name: String,
+    pub session: GameSession,
+}
+
+#[derive(Debug, Clone)]
+pub struct HotSeat {
+    players: Vec<Player>,
+    current: usize,
+    winner: Option<usize>,
+}
+
+impl HotSeat {
+    pub fn new(names: Vec<String>, difficulty: Difficulty, numbers: &mut dyn NumberSource) -> HotSeat {
+        let secret_number = numbers.pick(difficulty.range());
+        HotSeat::with_secret(names, difficulty, secret_number)
+    }
+
+    pub fn with_secret(names: Vec<String>, difficulty: Difficulty, secret_number: u32) -> HotSeat {
+        assert!(!names.is_empty(), "A hot-seat game needs at least one player.");
+
+        let players = names
+            .into_iter()
+            .map(|name| Player {
+                name,
+                session: GameSession::with_secret(difficulty, secret_number),
+            })
+            .collect();
+        HotSeat { players, current: 0, winner: None }
+    }
+
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    pub fn current_player(&self) -> &Player {
+        &self.players[self.current]
+    }
+
+    // 只有被计数的猜测才会轮到下一位，输入错误的玩家可以重新输入
+    pub fn submit_guess(&mut self, input: &str) -> Outcome {
+        let outcome = self.players[self.current].session.submit_guess(input);
+        match outcome {
+            Outcome::Win => self.winner = Some(self.current),
+            Outcome::TooSmall | Outcome::TooBig => self.next_turn(),
+            Outcome::Invalid(_) | Outcome::OutOfRange(_) | Outcome::GameOver => {}
+        }
+        outcome
+    }
+
+    // 跳过已经用完次数的玩家
+    fn next_turn(&mut self) {
+        for step in 1..=self.players.len() {
+            let next = (self.current + step) % self.players.len();
+            if !self.players[next].session.is_over() {
+                self.current = next;
+                return;
+            }
+        }
+    }
+
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    pub fn winner(&self) -> Option<&Player> {
+        self.winner.map(|index| &self.players[index])
+    }
+
+    pub fn secret_number(&self) -> u32 {
+        self.players[0].session.secret_number()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.winner.is_some() || self.players.iter().all(|player| player.session.is_over())
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+// 反向模式：玩家心里想一个数字，由电脑用二分查找来猜
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reply {
+    Higher,
+    Lower,
+    Correct,
+}
+
+// 无法识别的回答，保存玩家原本的输入
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseReplyError(pub String);
+
+impl FromStr for Reply {
+    type Err = ParseReplyError;
+
+    fn from_str(s: &str) -> Result<Reply, ParseReplyError> {
+        match s.trim().to_lowercase().as_str() {
+            "h" | "higher" | "+" | "大" => Ok(Reply::Higher),
+            "l" | "lower" | "-" | "小" => Ok(Reply::Lower),
+            "c" | "correct" | "=" | "对" => Ok(Reply::Correct),
+            other => Err(ParseReplyError(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ParseReplyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Please answer higher, lower or correct, not '{}'.", self.0)
+    }
+}
+
+// 玩家的回答互相矛盾时返回，记录矛盾发生时的区间和那次回答
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cheating {
+    pub low: u32,
+    pub high: u32,
+    pub guess: u32,
+    pub reply: Reply,
+}
+
+impl fmt::Display for Cheating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let direction = if self.reply == Reply::Higher { "higher" } else { "lower" };
+        write!(
+            f,
+            "You're cheating! Your answers only leave {} to {}, so it can't be {direction} than {}.",
+            self.low, self.high, self.guess
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ComputerGuesser {
+    low: u32,
+    high: u32,
+    guesses: u32,
+    found: bool,
+}
+
+impl ComputerGuesser {
+    pub fn new(range: RangeInclusive<u32>) -> ComputerGuesser {
+        ComputerGuesser {
+            low: *range.start(),
+            high: *range.end(),
+            guesses: 0,
+            found: false,
+        }
+    }
+
+    pub fn current_guess(&self) -> u32 {
+        self.low + (self.high - self.low) / 2
+    }
+
+    pub fn guesses(&self) -> u32 {
+        self.guesses
+    }
+
+    pub fn is_found(&self) -> bool {
+        self.found
+    }
+
+    // 根据回答缩小区间，区间被缩成空的说明玩家前后说法矛盾
+    pub fn reply(&mut self, reply: Reply) -> Result<(), Cheating> {
+        let guess = self.current_guess();
+        let cheating = Cheating { low: self.low, high: self.high, guess, reply };
+        self.guesses += 1;
+
+        match reply {
+            Reply::Correct => self.found = true,
+            Reply::Higher if guess < self.high => self.low = guess + 1,
+            Reply::Lower if guess > self.low => self.high = guess - 1,
+            Reply::Higher | Reply::Lower => return Err(cheating),
+        }
+        Ok(())
+    }
+}
+
+// 二分查找最坏情况下需要的次数：floor(log2(n)) + 1
+pub fn max_guesses(range: &RangeInclusive<u32>) -> u32 {
+    let count = u64::from(*range.end()) - u64::from(*range.start()) + 1;
+    u64::BITS - count.leading_zeros()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 模拟一个诚实的玩家，返回电脑猜中时用了几次
+    fn play(range: RangeInclusive<u32>, secret: u32) -> u32 {
+        let mut guesser = ComputerGuesser::new(range);
+        while !guesser.is_found() {
+            let reply = match secret.cmp(&guesser.current_guess()) {
+                std::cmp::Ordering::Less => Reply::Lower,
+                std::cmp::Ordering::Greater => Reply::Higher,
+                std::cmp::Ordering::Equal => Reply::Correct,
+            };
+            guesser.reply(reply).expect("an honest player never cheats");
+        }
+        assert_eq!(guesser.current_guess(), secret);
+        guesser.guesses()
+    }
+
+    #[test]
+    fn replies_parse_in_english_chinese_and_symbols() {
+        for (input, reply) in [
+            ("h", Reply::Higher),
+            (" Higher\n", Reply::Higher),
+            ("+", Reply::Higher),
+            ("大", Reply::Higher),
+            ("L", Reply::Lower),
+            ("lower", Reply::Lower),
+            ("-", Reply::Lower),
+            ("小", Reply::Lower),
+            ("c", Reply::Correct),
+            ("CORRECT", Reply::Correct),
+            ("=", Reply::Correct),
+            ("对", Reply::Correct),
+        ] {
+            assert_eq!(input.parse(), Ok(reply), "{input:?}");
+        }
+        assert_eq!("maybe".parse::<Reply>(), Err(ParseReplyError(String::from("maybe"))));
+        assert_eq!("".parse::<Reply>(), Err(ParseReplyError(String::new())));
+    }
+
+    #[test]
+    fn max_guesses_is_the_binary_search_bound() {
+        assert_eq!(max_guesses(&(1..=1)), 1);
+        assert_eq!(max_guesses(&(1..=2)), 2);
+        assert_eq!(max_guesses(&(1..=3)), 2);
+        assert_eq!(max_guesses(&(1..=4)), 3);
+        assert_eq!(max_guesses(&(1..=100)), 7);
+        assert_eq!(max_guesses(&(1..=1000)), 10);
+        assert_eq!(max_guesses(&(0..=u32::MAX)), 33);
+    }
+
+    #[test]
+    fn finds_every_number_within_the_bound() {
+        for range in [1..=1, 1..=2, 1..=3, 1..=100, 7..=1000] {
+            let bound = max_guesses(&range);
+            for secret in range.clone() {
+                assert!(play(range.clone(), secret) <= bound, "{secret} in {range:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn handles_the_ends_of_u32() {
+        let range = 0..=u32::MAX;
+        for secret in [0, 1, u32::MAX - 1, u32::MAX] {
+            assert!(play(range.clone(), secret) <= max_guesses(&range));
+        }
+        assert_eq!(play(u32::MAX..=u32::MAX, u32::MAX), 1);
+    }
+
+    #[test]
+    fn contradictions_are_cheating() {
+        // 只剩一个数字时既不能更大也不能更小
+        let mut single = ComputerGuesser::new(5..=5);
+        let cheating = Cheating { low: 5, high: 5, guess: 5, reply: Reply::Higher };
+        assert_eq!(single.reply(Reply::Higher), Err(cheating));
+        assert_eq!(single.reply(Reply::Lower), Err(Cheating { reply: Reply::Lower, ..cheating }));
+        assert!(!single.is_found());
+        assert_eq!(single.reply(Reply::Correct), Ok(()));
+        assert!(single.is_found());
+
+        // 猜的正好是下界时不能再往小猜，区间保持不变
+        let mut pair = ComputerGuesser::new(1..=2);
+        assert_eq!(pair.current_guess(), 1);
+        assert_eq!(pair.reply(Reply::Lower), Err(Cheating { low: 1, high: 2, guess: 1, reply: Reply::Lower }));
+        assert_eq!(pair.current_guess(), 1);
+        assert_eq!(pair.reply(Reply::Higher), Ok(()));
+        assert_eq!(pair.current_guess(), 2);
+        // 猜的正好是上界时不能再往大猜
+        assert_eq!(pair.reply(Reply::Higher), Err(Cheating { low: 2, high: 2, guess: 2, reply: Reply::Higher }));
+        assert_eq!(pair.guesses(), 3);
+    }
+
+    #[test]
+    fn inconsistent_answers_are_caught() {
+        // 先说比 50 大，再说比 51 小
+        let mut guesser = ComputerGuesser::new(1..=100);
+        assert_eq!(guesser.current_guess(), 50);
+        guesser.reply(Reply::Higher).unwrap();
+        let mut result = Ok(());
+        while result.is_ok() {
+            result = guesser.reply(Reply::Lower);
+        }
+        assert_eq!(result, Err(Cheating { low: 51, high: 52, guess: 51, reply: Reply::Lower }));
+    }
+}
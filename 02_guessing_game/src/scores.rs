@@ -0,0 +1,201 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::Difficulty;
+
+// 每次获胜记录一行，字段之间用制表符分隔：玩家 难度 次数 毫秒
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Score {
+    pub player: String,
+    pub difficulty: String,
+    pub attempts: u32,
+    pub elapsed: Duration,
+}
+
+impl Score {
+    fn to_line(&self) -> String {
+        let player: String = self.player
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect();
+        format!("{}\t{}\t{}\t{}", player.trim(), self.difficulty, self.attempts, self.elapsed.as_millis())
+    }
+
+    fn from_line(line: &str) -> Option<Score> {
+        let mut fields = line.split('\t');
+        let player = fields.next()?.to_string();
+        let difficulty = fields.next()?.to_string();
+        let attempts = fields.next()?.parse().ok()?;
+        let millis = fields.next()?.parse().ok()?;
+        if fields.next().is_some() || player.is_empty() || difficulty.is_empty() {
+            return None;
+        }
+        Some(Score { player, difficulty, attempts, elapsed: Duration::from_millis(millis) })
+    }
+}
+
+// 读取结果：能解析的分数，以及被跳过的损坏行的行号（从 1 开始）
+#[derive(Debug, Clone, Default)]
+pub struct ScoreFile {
+    pub scores: Vec<Score>,
+    pub corrupt_lines: Vec<usize>,
+}
+
+// 文件不存在时返回空表，损坏的行会被跳过
+pub fn load(path: &Path) -> io::Result<ScoreFile> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(ScoreFile::default()),
+        Err(error) => return Err(error),
+    };
+
+    let mut file = ScoreFile::default();
+    for (number, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match Score::from_line(line) {
+            Some(score) => file.scores.push(score),
+            None => file.corrupt_lines.push(number + 1),
+        }
+    }
+    Ok(file)
+}
+
+pub fn record(path: &Path, score: &Score) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", score.to_line())
+}
+
+// 自定义难度连同范围和次数一起保存，例如 custom 1-1000/10，
+// 这样设置不同的自定义成绩不会被放在一起比较
+pub fn difficulty_key(difficulty: Difficulty) -> String {
+    match difficulty {
+        Difficulty::Custom { min, max, max_attempts } => format!("custom {min}-{max}/{max_attempts}"),
+        preset => preset.to_string(),
+    }
+}
+
+// 排行榜顺序：难度从难到易，自定义难度排在最后并按设置分组，
+// 同一组里次数少的在前，再比较用时
+pub fn sort_leaderboard(scores: &mut [Score]) {
+    scores.sort_by(|a, b| {
+        difficulty_rank(&a.difficulty)
+            .cmp(&difficulty_rank(&b.difficulty))
+            .then_with(|| a.difficulty.cmp(&b.difficulty))
+            .then(a.attempts.cmp(&b.attempts))
+            .then(a.elapsed.cmp(&b.elapsed))
+    });
+}
+
+fn difficulty_rank(difficulty: &str) -> u8 {
+    match difficulty {
+        "hard" => 0,
+        "normal" => 1,
+        "easy" => 2,
+        _ => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    fn score(player: &str, difficulty: &str, attempts: u32, millis: u64) -> Score {
+        Score { player: player.to_string(), difficulty: difficulty.to_string(), attempts, elapsed: Duration::from_millis(millis) }
+    }
+
+    // 每个测试用自己的文件名，测试并行运行时互不干扰
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        env::temp_dir().join(format!("guessing_game_scores_{}_{name}.txt", process::id()))
+    }
+
+    #[test]
+    fn lines_round_trip() {
+        let original = score("alice", "hard", 7, 12_345);
+        assert_eq!(Score::from_line(&original.to_line()), Some(original));
+    }
+
+    #[test]
+    fn tabs_and_control_characters_in_names_are_replaced() {
+        let line = score(" bob\tthe\nbuilder\u{7} ", "easy", 3, 10).to_line();
+        assert_eq!(line, "bob the builder\teasy\t3\t10");
+        assert_eq!(Score::from_line(&line).unwrap().player, "bob the builder");
+    }
+
+    #[test]
+    fn corrupt_lines_are_rejected() {
+        for line in [
+            "",
+            "alice",
+            "alice\thard\t7",
+            "alice\thard\tseven\t100",
+            "alice\thard\t7\t1.5",
+            "alice\thard\t-7\t100",
+            "alice\thard\t7\t100\textra",
+            "\thard\t7\t100",
+            "alice\t\t7\t100",
+        ] {
+            assert_eq!(Score::from_line(line), None, "{line:?}");
+        }
+    }
+
+    #[test]
+    fn missing_file_is_an_empty_table() {
+        let path = temp_path("missing");
+        let _ = fs::remove_file(&path);
+        let file = load(&path).unwrap();
+        assert!(file.scores.is_empty());
+        assert!(file.corrupt_lines.is_empty());
+    }
+
+    #[test]
+    fn corrupt_lines_are_skipped_and_reported() {
+        let path = temp_path("corrupt");
+        fs::write(&path, "alice\thard\t7\t100\ngarbage\n\nbob\teasy\t3\t50\nbob\teasy\tx\t50\n").unwrap();
+        let file = load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(file.scores, [score("alice", "hard", 7, 100), score("bob", "easy", 3, 50)]);
+        assert_eq!(file.corrupt_lines, [2, 5]);
+    }
+
+    #[test]
+    fn record_appends_to_the_file() {
+        let path = temp_path("record");
+        let _ = fs::remove_file(&path);
+        record(&path, &score("alice", "hard", 7, 100)).unwrap();
+        record(&path, &score("bob", "easy", 3, 50)).unwrap();
+        let file = load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(file.scores.len(), 2);
+        assert!(file.corrupt_lines.is_empty());
+    }
+
+    #[test]
+    fn custom_difficulties_keep_their_settings() {
+        assert_eq!(difficulty_key(Difficulty::Hard), "hard");
+        assert_eq!(difficulty_key(Difficulty::custom(1, 1000, 10).unwrap()), "custom 1-1000/10");
+    }
+
+    #[test]
+    fn leaderboard_groups_presets_then_each_custom_setting() {
+        let mut scores = vec![
+            score("a", "custom 1-3/2", 2, 1000),
+            score("b", "custom 1-1000000/30", 8, 1000),
+            score("c", "easy", 1, 10),
+            score("d", "hard", 9, 500),
+            score("e", "custom 1-3/2", 1, 100),
+            score("f", "hard", 9, 400),
+            score("g", "normal", 5, 100),
+            score("h", "custom", 1, 1),
+        ];
+        sort_leaderboard(&mut scores);
+        let order: Vec<&str> = scores.iter().map(|score| score.player.as_str()).collect();
+        // 同一难度里先比次数再比用时；自定义难度排在预设后面，设置不同的分开排
+        assert_eq!(order, ["f", "d", "g", "c", "h", "b", "e", "a"]);
+    }
+}
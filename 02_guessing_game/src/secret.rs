@@ -0,0 +1,22 @@
+use std::ops::RangeInclusive;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// 秘密数字的来源，可以替换成固定种子的随机数生成器，方便重现和测试
+pub trait NumberSource {
+    fn pick(&mut self, range: RangeInclusive<u32>) -> u32;
+}
+
+impl<R: Rng> NumberSource for R {
+    fn pick(&mut self, range: RangeInclusive<u32>) -> u32 {
+        self.gen_range(range)
+    }
+}
+
+// 给定种子时每次运行都会得到相同的秘密数字
+pub fn source(seed: Option<u64>) -> Box<dyn NumberSource> {
+    match seed {
+        Some(seed) => Box::new(StdRng::seed_from_u64(seed)),
+        None => Box::new(rand::thread_rng()),
+    }
+}
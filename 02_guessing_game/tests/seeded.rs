@@ -0,0 +1,70 @@
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use guessing_game::secret;
+
+// 用和游戏相同的数字来源从种子推出秘密数字
+fn secret_for(seed: u64, min: u32, max: u32) -> u32 {
+    secret::source(Some(seed)).pick(min..=max)
+}
+
+// 每个测试在自己的临时目录里运行，避免把分数写进项目目录
+fn run_game(name: &str, args: &[&str], input: &str) -> String {
+    let dir: PathBuf = env::temp_dir().join(format!("guessing_game_{name}_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_guessing_game"))
+        .args(args)
+        .current_dir(&dir)
+        .env("LANG", "C")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start the game");
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn same_seed_same_secret() {
+    let secret = secret_for(42, 1, 100);
+    let input = format!("{secret}\ntester\n");
+
+    let stdout = run_game("win", &["--seed", "42", "-d", "normal"], &input);
+
+    assert!(stdout.contains(&format!("You guessed: {secret}")));
+    assert!(stdout.contains("You win!"));
+}
+
+#[test]
+fn scripted_guesses_narrow_down() {
+    let secret = secret_for(7, 1, 1000);
+    let low = secret - 1;
+    let high = secret + 1;
+    let input = format!("{low}\nnot a number\n{high}\n{secret}\ntester\n");
+
+    let stdout = run_game("narrow", &["--seed", "7", "--difficulty", "hard"], &input);
+
+    assert!(stdout.contains("Too small!"));
+    assert!(stdout.contains("Too big!"));
+    assert!(stdout.contains("You win!"));
+    assert!(stdout.contains("You needed 3 attempts"));
+}
+
+#[test]
+fn running_out_of_attempts_reveals_secret() {
+    let secret = secret_for(3, 1, 10);
+    let wrong = if secret == 1 { 2 } else { 1 };
+    let input = format!("{wrong}\n{wrong}\n");
+
+    let stdout = run_game("lose", &["--seed", "3", "--min", "1", "--max", "10", "--attempts", "2"], &input);
+
+    assert!(!stdout.contains("You win!"));
+    assert!(stdout.contains(&format!("the secret number was {secret}.")));
+}
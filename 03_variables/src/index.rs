@@ -0,0 +1,75 @@
+// 数组下标查询：支持负数下标（从末尾数起）和 1..3、1..=3、..2、3.. 这样的范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Query {
+    Index(isize),
+    Range { start: Option<isize>, end: Option<isize>, inclusive: bool },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    NotANumber(String),
+    OutOfBounds { index: isize, len: usize },
+    Reversed { start: usize, end: usize },
+}
+
+pub fn parse_query(input: &str) -> Result<Query, QueryError> {
+    let input = input.trim();
+    let (start, end, inclusive) = match input.split_once("..") {
+        None => return parse_index(input).map(Query::Index),
+        Some((start, end)) => match end.strip_prefix('=') {
+            Some(end) => (start, end, true),
+            None => (start, end, false),
+        },
+    };
+
+    let bound = |text: &str| {
+        if text.trim().is_empty() {
+            Ok(None)
+        } else {
+            parse_index(text).map(Some)
+        }
+    };
+    Ok(Query::Range { start: bound(start)?, end: bound(end)?, inclusive })
+}
+
+fn parse_index(text: &str) -> Result<isize, QueryError> {
+    text.trim().parse().map_err(|_| QueryError::NotANumber(text.trim().to_string()))
+}
+
+// 把可能为负的下标换算成真正的位置，allow_end 为 true 时允许等于 len（范围的右端点）
+fn resolve(index: isize, len: usize, allow_end: bool) -> Result<usize, QueryError> {
+    let position = if index < 0 {
+        len.checked_sub(index.unsigned_abs())
+    } else {
+        Some(index.unsigned_abs())
+    };
+    match position {
+        Some(position) if position < len || (allow_end && position == len) => Ok(position),
+        _ => Err(QueryError::OutOfBounds { index, len }),
+    }
+}
+
+pub fn lookup(array: &[i32], query: Query) -> Result<&[i32], QueryError> {
+    let len = array.len();
+    match query {
+        Query::Index(index) => {
+            let position = resolve(index, len, false)?;
+            array.get(position..=position).ok_or(QueryError::OutOfBounds { index, len })
+        }
+        Query::Range { start, end, inclusive } => {
+            let start = match start {
+                Some(start) => resolve(start, len, true)?,
+                None => 0,
+            };
+            let end = match end {
+                Some(end) if inclusive => resolve(end, len, false)? + 1,
+                Some(end) => resolve(end, len, true)?,
+                None => len,
+            };
+            if start > end {
+                return Err(QueryError::Reversed { start, end });
+            }
+            array.get(start..end).ok_or(QueryError::Reversed { start, end })
+        }
+    }
+}
@@ -108,16 +108,52 @@
 //     println!("first: {first}, second: {second}")
 // }
 
+use std::env;
 use std::io;
-// 无效的数组访问
-fn  main() {
+use std::process;
+
+use crate::index::Query;
+use crate::messages::{Lang, Message};
+
+mod index;
+mod messages;
+
+// 安全的数组访问：用 get 检查下标，越界时提示有效范围而不是 panic
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let lang = Lang::detect(&args);
+    if let Err(tag) = Lang::from_args(&args) {
+        eprintln!("{}", Message::UnknownLang(&tag).text(lang));
+        process::exit(2);
+    }
     let a = [1,2,3,4,5,6];
-    println!("Please enter an array index：");
-    let mut index = String::new();
-    io::stdin().read_line(&mut index)
-        .expect("Failed to read line");
-    let index: usize = index.trim().parse()
-        .expect("Index entered was not a number");
-    let element = a[index];
-    println!("The value of the element at index {index} is: {element}");
-}
\ No newline at end of file
+    println!("{}", Message::ArrayIs(&a).text(lang));
+
+    loop {
+        println!("{}", Message::AskIndex.text(lang));
+        let mut input = String::new();
+        match io::stdin().read_line(&mut input) {
+            Ok(0) => {
+                println!("{}", Message::Bye.text(lang));
+                break;
+            }
+            Ok(_) => {}
+            Err(error) => {
+                eprintln!("{}", Message::ReadFailed(&error).text(lang));
+                break;
+            }
+        }
+        if input.trim().is_empty() {
+            continue;
+        }
+
+        let result = index::parse_query(&input)
+            .and_then(|query| index::lookup(&a, query).map(|values| (query, values)));
+        let message = match result {
+            Ok((Query::Index(index), values)) => Message::Element { index, element: values[0] },
+            Ok((Query::Range { .. }, values)) => Message::Slice { query: input.trim(), values },
+            Err(ref error) => Message::Query(error),
+        };
+        println!("{}", message.text(lang));
+    }
+}
@@ -0,0 +1,63 @@
+use std::io;
+
+use crate::index::QueryError;
+
+pub use lang::Lang;
+
+pub enum Message<'a> {
+    ArrayIs(&'a [i32]),
+    AskIndex,
+    ReadFailed(&'a io::Error),
+    Element { index: isize, element: i32 },
+    Slice { query: &'a str, values: &'a [i32] },
+    Query(&'a QueryError),
+    Bye,
+    UnknownLang(&'a str),
+}
+
+impl Message<'_> {
+    pub fn text(&self, lang: Lang) -> String {
+        match (lang, self) {
+            (Lang::En, Message::ArrayIs(array)) => format!("The array is {array:?}."),
+            (Lang::En, Message::AskIndex) => {
+                String::from("Please enter an array index (negative counts from the end) or a range like 1..3：")
+            }
+            (Lang::En, Message::ReadFailed(error)) => format!("Failed to read line: {error}"),
+            (Lang::En, Message::Element { index, element }) => {
+                format!("The value of the element at index {index} is: {element}")
+            }
+            (Lang::En, Message::Slice { query, values }) => format!("The slice {query} is: {values:?}"),
+            (Lang::En, Message::Query(QueryError::NotANumber(input))) => {
+                format!("'{input}' is not an index, try a number like 2 or -1, or a range like 1..3.")
+            }
+            (Lang::En, Message::Query(QueryError::OutOfBounds { index, len })) => format!(
+                "Index {index} is out of bounds, valid indices are 0 to {} or -{len} to -1.",
+                len.saturating_sub(1)
+            ),
+            (Lang::En, Message::Query(QueryError::Reversed { start, end })) => {
+                format!("The range starts at {start} but ends at {end}, the start must not be after the end.")
+            }
+            (Lang::En, Message::Bye) => String::from("Bye!"),
+            (Lang::En, Message::UnknownLang(tag)) => format!("Unknown language '{tag}', expected en or zh."),
+            (Lang::Zh, Message::ArrayIs(array)) => format!("数组是 {array:?}。"),
+            (Lang::Zh, Message::AskIndex) => String::from("请输入数组下标（负数表示从末尾数起）或 1..3 这样的范围："),
+            (Lang::Zh, Message::ReadFailed(error)) => format!("读取输入失败：{error}"),
+            (Lang::Zh, Message::Element { index, element }) => {
+                format!("下标 {index} 处的元素是：{element}")
+            }
+            (Lang::Zh, Message::Slice { query, values }) => format!("切片 {query} 是：{values:?}"),
+            (Lang::Zh, Message::Query(QueryError::NotANumber(input))) => {
+                format!("'{input}' 不是下标，请输入 2、-1 这样的数字或 1..3 这样的范围。")
+            }
+            (Lang::Zh, Message::Query(QueryError::OutOfBounds { index, len })) => format!(
+                "下标 {index} 越界了，有效的下标是 0 到 {} 或 -{len} 到 -1。",
+                len.saturating_sub(1)
+            ),
+            (Lang::Zh, Message::Query(QueryError::Reversed { start, end })) => {
+                format!("范围从 {start} 开始却在 {end} 结束，起点不能在终点之后。")
+            }
+            (Lang::Zh, Message::Bye) => String::from("再见！"),
+            (Lang::Zh, Message::UnknownLang(tag)) => format!("未知语言 '{tag}'，可选 en 或 zh。"),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+// 比较四叉树和逐个检查的朴素做法，运行：cargo bench
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use structs::rectangle::Rectangle;
+use structs::spatial::QuadTree;
+
+const WORLD: u32 = 10_000;
+
+// 简单的线性同余生成器，保证每次运行的数据都一样
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: u32) -> u32 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 33) % u64::from(bound)) as u32
+    }
+
+    fn rectangle(&mut self, max_size: u32) -> Rectangle {
+        let x = self.next(WORLD) as i32;
+        let y = self.next(WORLD) as i32;
+        Rectangle::at(x, y, 1 + self.next(max_size), 1 + self.next(max_size))
+    }
+}
+
+fn time<T>(label: &str, run: impl FnOnce() -> T) -> (T, Duration) {
+    let started = Instant::now();
+    let result = black_box(run());
+    let elapsed = started.elapsed();
+    println!("{label:<40} {elapsed:>12.3?}");
+    (result, elapsed)
+}
+
+fn main() {
+    for count in [1_000, 10_000, 100_000] {
+        let mut random = Lcg(count as u64);
+        let rects: Vec<Rectangle> = (0..count).map(|_| random.rectangle(50)).collect();
+        let queries: Vec<Rectangle> = (0..1_000).map(|_| random.rectangle(200)).collect();
+        println!("{count} rectangles, {} overlap queries", queries.len());
+
+        let (tree, _) = time("  quadtree: build", || {
+            let mut tree = QuadTree::new(Rectangle::new(WORLD + 50, WORLD + 50));
+            for rect in &rects {
+                tree.insert(*rect);
+            }
+            tree
+        });
+
+        let (indexed, indexed_time) = time("  quadtree: overlap queries", || {
+            queries.iter().map(|query| tree.query_overlap(query).len()).sum::<usize>()
+        });
+        let (naive, naive_time) = time("  naive scan: overlap queries", || {
+            queries
+                .iter()
+                .map(|query| rects.iter().filter(|rect| rect.intersects(query)).count())
+                .sum::<usize>()
+        });
+        time("  quadtree: point queries", || {
+            queries.iter().map(|query| tree.query_point(query.x, query.y).len()).sum::<usize>()
+        });
+        time("  naive scan: point queries", || {
+            queries
+                .iter()
+                .map(|query| rects.iter().filter(|rect| rect.contains_point(query.x, query.y)).count())
+                .sum::<usize>()
+        });
+
+        assert_eq!(indexed, naive, "quadtree and naive scan disagree");
+        println!(
+            "  speedup for overlap queries: {:.1}x\n",
+            naive_time.as_secs_f64() / indexed_time.as_secs_f64().max(f64::EPSILON)
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+
+// 和教程里一样是三个 i32 的元组结构体，分量可以暂时超出 0..=255，用 clamped 收回来
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Color(pub i32, pub i32, pub i32);
+
+// hue 是角度 0..360，saturation 和 lightness 在 0..=1 之间
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub hue: f64,
+    pub saturation: f64,
+    pub lightness: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    MissingHash(String),
+    WrongLength(String),
+    InvalidHex(String),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseColorError::MissingHash(input) => write!(f, "'{input}' should start with '#', such as #FF8800"),
+            ParseColorError::WrongLength(input) => write!(f, "'{input}' should have exactly 6 hex digits after '#'"),
+            ParseColorError::InvalidHex(input) => write!(f, "'{input}' contains characters that are not hex digits"),
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+impl Color {
+    pub const BLACK: Color = Color(0, 0, 0);
+    pub const WHITE: Color = Color(255, 255, 255);
+
+    pub fn clamped(&self) -> Color {
+        Color(clamp(self.0), clamp(self.1), clamp(self.2))
+    }
+
+    pub fn is_valid(&self) -> bool {
+        *self == self.clamped()
+    }
+
+    // 超出范围的分量先截到 0..=255 再输出
+    pub fn to_hex(&self) -> String {
+        let Color(r, g, b) = self.clamped();
+        format!("#{r:02X}{g:02X}{b:02X}")
+    }
+
+    pub fn to_hsl(&self) -> Hsl {
+        let Color(r, g, b) = self.clamped();
+        let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+        if delta == 0.0 {
+            return Hsl { hue: 0.0, saturation: 0.0, lightness };
+        }
+
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        Hsl { hue, saturation, lightness }
+    }
+
+    pub fn from_hsl(hsl: Hsl) -> Color {
+        let hue = hsl.hue.rem_euclid(360.0);
+        let saturation = hsl.saturation.clamp(0.0, 1.0);
+        let lightness = hsl.lightness.clamp(0.0, 1.0);
+
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let x = chroma * (1.0 - ((hue / 60.0).rem_euclid(2.0) - 1.0).abs());
+        let (r, g, b) = match (hue / 60.0) as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = lightness - chroma / 2.0;
+        let channel = |value: f64| ((value + m) * 255.0).round() as i32;
+        Color(channel(r), channel(g), channel(b)).clamped()
+    }
+
+    // t = 0 得到 self，t = 1 得到 other，中间按比例线性混合
+    pub fn blend(&self, other: &Color, t: f64) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: i32, b: i32| (a as f64 + (b as f64 - a as f64) * t).round() as i32;
+        Color(mix(self.0, other.0), mix(self.1, other.1), mix(self.2, other.2))
+    }
+}
+
+fn clamp(value: i32) -> i32 {
+    value.clamp(0, 255)
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&self.to_hex())
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Color, ParseColorError> {
+        let input = s.trim();
+        let digits = input.strip_prefix('#').ok_or_else(|| ParseColorError::MissingHash(input.to_string()))?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ParseColorError::InvalidHex(input.to_string()));
+        }
+        if digits.len() != 6 {
+            return Err(ParseColorError::WrongLength(input.to_string()));
+        }
+        let channel = |range| i32::from_str_radix(&digits[range], 16).expect("checked hex digits");
+        Ok(Color(channel(0..2), channel(2..4), channel(4..6)))
+    }
+}
+
+// 加减按分量进行，结果可能超出 0..=255
+impl Add for Color {
+    type Output = Color;
+
+    fn add(self, other: Color) -> Color {
+        Color(self.0.saturating_add(other.0), self.1.saturating_add(other.1), self.2.saturating_add(other.2))
+    }
+}
+
+impl Sub for Color {
+    type Output = Color;
+
+    fn sub(self, other: Color) -> Color {
+        Color(self.0.saturating_sub(other.0), self.1.saturating_sub(other.1), self.2.saturating_sub(other.2))
+    }
+}
+
+impl Mul<f64> for Color {
+    type Output = Color;
+
+    fn mul(self, factor: f64) -> Color {
+        let scale = |value: i32| (value as f64 * factor).round() as i32;
+        Color(scale(self.0), scale(self.1), scale(self.2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_parses_and_prints() {
+        assert_eq!("#FF8800".parse(), Ok(Color(255, 136, 0)));
+        assert_eq!(" #ff8800 ".parse(), Ok(Color(255, 136, 0)));
+        assert_eq!(Color(255, 136, 0).to_hex(), "#FF8800");
+        assert_eq!(Color(0, 1, 15).to_string(), "#00010F");
+        // 超出范围的分量先截断再输出
+        assert_eq!(Color(300, -20, 128).to_string(), "#FF0080");
+    }
+
+    #[test]
+    fn hex_parse_errors() {
+        let error = |input: &str| input.parse::<Color>().unwrap_err();
+        assert_eq!(error("FF8800"), ParseColorError::MissingHash(String::from("FF8800")));
+        assert_eq!(error(""), ParseColorError::MissingHash(String::new()));
+        assert_eq!(error("#FF88"), ParseColorError::WrongLength(String::from("#FF88")));
+        assert_eq!(error("#FF880000"), ParseColorError::WrongLength(String::from("#FF880000")));
+        assert_eq!(error("#"), ParseColorError::WrongLength(String::from("#")));
+        assert_eq!(error("#GG8800"), ParseColorError::InvalidHex(String::from("#GG8800")));
+        assert_eq!(error("#+F8800"), ParseColorError::InvalidHex(String::from("#+F8800")));
+        assert_eq!(error("#FF88é0"), ParseColorError::InvalidHex(String::from("#FF88é0")));
+    }
+
+    #[test]
+    fn primary_colours_convert_to_hsl_and_back() {
+        let cases = [
+            (Color(255, 0, 0), 0.0),
+            (Color(0, 255, 0), 120.0),
+            (Color(0, 0, 255), 240.0),
+            (Color(255, 255, 0), 60.0),
+            (Color(0, 255, 255), 180.0),
+            (Color(255, 0, 255), 300.0),
+        ];
+        for (color, hue) in cases {
+            let hsl = color.to_hsl();
+            assert_eq!(hsl, Hsl { hue, saturation: 1.0, lightness: 0.5 }, "{color}");
+            assert_eq!(Color::from_hsl(hsl), color);
+        }
+    }
+
+    #[test]
+    fn greys_have_no_saturation() {
+        for level in [0, 1, 64, 128, 200, 254, 255] {
+            let grey = Color(level, level, level);
+            let hsl = grey.to_hsl();
+            assert_eq!(hsl.saturation, 0.0, "{grey}");
+            assert_eq!(hsl.lightness, level as f64 / 255.0, "{grey}");
+            assert_eq!(Color::from_hsl(hsl), grey);
+        }
+    }
+
+    #[test]
+    fn every_colour_survives_the_hsl_round_trip() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let color = Color(r, g, b);
+                    assert_eq!(Color::from_hsl(color.to_hsl()), color);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_hsl_wraps_hue_and_clamps_the_rest() {
+        let red = Color(255, 0, 0);
+        assert_eq!(Color::from_hsl(Hsl { hue: 360.0, saturation: 1.0, lightness: 0.5 }), red);
+        assert_eq!(Color::from_hsl(Hsl { hue: -360.0, saturation: 2.0, lightness: 0.5 }), red);
+        assert_eq!(Color::from_hsl(Hsl { hue: 0.0, saturation: 1.0, lightness: 1.5 }), Color::WHITE);
+    }
+
+    #[test]
+    fn blend_endpoints_and_midpoint() {
+        let orange = Color(255, 136, 0);
+        assert_eq!(orange.blend(&Color::WHITE, 0.0), orange);
+        assert_eq!(orange.blend(&Color::WHITE, 1.0), Color::WHITE);
+        assert_eq!(Color::BLACK.blend(&Color::WHITE, 0.5), Color(128, 128, 128));
+        // t 超出 0..=1 时按端点处理
+        assert_eq!(orange.blend(&Color::WHITE, -1.0), orange);
+        assert_eq!(orange.blend(&Color::WHITE, 2.0), Color::WHITE);
+    }
+
+    #[test]
+    fn clamping_and_arithmetic() {
+        assert_eq!(Color(300, -20, 128).clamped(), Color(255, 0, 128));
+        assert!(!Color(300, 0, 0).is_valid());
+        assert!(Color(255, 0, 0).is_valid());
+        assert_eq!(Color(200, 100, 0) + Color(100, 100, 100), Color(300, 200, 100));
+        assert_eq!(Color(10, 10, 10) - Color(20, 0, 0), Color(-10, 10, 10));
+        assert_eq!(Color(i32::MAX, 0, 0) + Color(1, 0, 0), Color(i32::MAX, 0, 0));
+        assert_eq!(Color(100, 50, 3) * 0.5, Color(50, 25, 2));
+    }
+}
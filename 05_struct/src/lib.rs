@@ -0,0 +1,8 @@
+pub mod color;
+pub mod packing;
+pub mod point;
+pub mod rectangle;
+pub mod render;
+pub mod shape;
+pub mod spatial;
+pub mod user;
@@ -83,12 +83,16 @@
 
 // method syntax
 
-#[allow(dead_code)]
-#[derive(Debug)]
-struct Rectangle {
-    width: u32,
-    height: u32,
-}
+use structs::color::{Color, Hsl};
+use structs::packing;
+use structs::point::Point;
+use structs::rectangle::Rectangle;
+use structs::render;
+use structs::shape::{self, Circle, Polygon, Shape, Triangle};
+use structs::spatial::QuadTree;
+use structs::user::jsonl;
+use structs::user::store::UserStore;
+use structs::user::User;
 
 //
 // impl Rectangle {
@@ -126,31 +130,164 @@ struct Rectangle {
 //     }
 // }
 
-#[allow(dead_code)]
-impl Rectangle {
-    fn area(&self) -> u32 {
-        self.width * self.height
+fn main() {
+    let rect1 = Rectangle::new(30, 50);
+    let rect2 = Rectangle::new(10, 40);
+    let rect3 = Rectangle::new(60, 45);
+
+    println!("Can rect1 hold rect2? {}", rect1.can_hold(&rect2));
+    println!("Can rect1 hold rect3? {}", rect1.can_hold(&rect3));
+
+    // 带位置的矩形
+    let a = Rectangle::at(0, 0, 30, 50);
+    let b = Rectangle::at(20, 40, 30, 30);
+    let c = Rectangle::at(30, 0, 10, 10);
+
+    println!("Does a intersect b? {}", a.intersects(&b));
+    println!("Intersection of a and b: {:?}", a.intersection(&b));
+    println!("Does a intersect c (touching edge)? {}", a.intersects(&c));
+    println!("Union bounds of a and b: {:?}", a.union_bounds(&b));
+    println!("Does a contain point (29, 49)? {}", a.contains_point(29, 49));
+    println!("Can a hold a same-sized rectangle? {}", a.can_hold_or_equal(&rect1));
+
+    // 不会溢出的计算
+    let huge = Rectangle::new(u32::MAX, u32::MAX);
+    println!("The area of huge is {} square pixels.", huge.area());
+    println!("The perimeter of huge is {}.", huge.perimeter());
+    println!("rect1 scaled by 3: {:?}", rect1.scale(3));
+    println!("huge scaled by 2: {:?}", huge.scale(2));
+    println!("Aspect ratio of rect1: {:?}", rect1.aspect_ratio());
+
+    // 装箱
+    let container = Rectangle::at(10, 10, 100, 60);
+    let items = [
+        Rectangle::new(50, 30),
+        Rectangle::new(30, 50),
+        Rectangle::new(40, 20),
+        Rectangle::new(60, 25),
+        Rectangle::new(120, 10),
+    ];
+    let result = packing::pack(&container, &items, true);
+    for placement in &result.placements {
+        println!("item {} placed at {:?} (rotated: {})", placement.index, placement.rect, placement.rotated);
     }
+    println!("items that did not fit: {:?}", result.unplaced);
 
-    fn can_hold(&self, other: &Rectangle) -> bool {
-        self.width > other.width && self.height > other.height
+    // 用字符画出来，每个字符代表 10 × 10
+    let drawn: Vec<Rectangle> = result.placements.iter().map(|placement| placement.rect).collect();
+    for (rects, scale) in [(drawn.as_slice(), 10), (&[a, b, c], 5), (&[Rectangle::new(u32::MAX, 1)], 1)] {
+        match render::render_ascii(rects, scale) {
+            Ok(picture) => print!("{picture}"),
+            Err(error) => println!("error: {error}"),
+        }
     }
-}
+    print!("{}", render::render_svg(&[a, b, c]));
 
-fn main() {
-    let rect1 = Rectangle {
-        width: 30,
-        height: 50,
-    };
-    let rect2 = Rectangle {
-        width: 10,
-        height: 40,
-    };
-    let rect3 = Rectangle {
-        width: 60,
-        height: 45,
-    };
+    // 文本格式
+    for text in ["30x50", "30 x 50", "30x50@-5,10", "0x50", "-3x5", "30by50", "30xabc"] {
+        match text.parse::<Rectangle>() {
+            Ok(rect) => println!("{text:?} -> {rect}"),
+            Err(error) => println!("{text:?} -> error: {error}"),
+        }
+    }
+    println!("b is {b}, round trip: {}", b.to_string().parse::<Rectangle>() == Ok(b));
 
-    println!("Can rect1 hold rect2? {}", rect1.can_hold(&rect2));
-    println!("Can rect1 hold rect3? {}", rect1.can_hold(&rect3));
+    // 空间索引
+    let mut index = QuadTree::new(Rectangle::new(100, 100));
+    let id_a = index.insert(a);
+    let id_b = index.insert(b);
+    index.insert(c);
+    println!("Rectangles at (25, 45): {:?}", index.query_point(25, 45));
+    println!("Rectangles overlapping (35, 5) 10x10: {:?}", index.query_overlap(&Rectangle::at(35, 5, 10, 10)));
+    index.remove(id_a);
+    println!("After removing {id_a:?}, rectangles at (25, 45): {:?}", index.query_point(25, 45));
+    println!("{id_b:?} is {:?}", index.get(id_b));
+
+    // 不同图形放在一起
+    let mut shapes: Vec<Box<dyn Shape>> = vec![
+        Box::new(Rectangle::at(0, 0, 30, 50)),
+        Box::new(Circle { x: 0.0, y: 0.0, radius: 10.0 }),
+        Box::new(Triangle { a: (0.0, 0.0), b: (4.0, 0.0), c: (0.0, 3.0) }),
+        Box::new(Polygon { vertices: vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (5.0, 15.0), (0.0, 10.0)] }),
+    ];
+    shape::sort_by_area(&mut shapes);
+    for shape in &shapes {
+        println!(
+            "{shape:?}: area {:.2}, perimeter {:.2}, bounding box {:?}",
+            shape.area(),
+            shape.perimeter(),
+            shape.bounding_box()
+        );
+    }
+    println!("Total area of all shapes: {:.2}", shape::total_area(&shapes));
+
+    // 带校验的用户
+    match User::new("someone@example.com", "someusername123") {
+        Ok(user) => println!("{} <{}> active: {}", user.username, user.email, user.active),
+        Err(error) => println!("error: {error}"),
+    }
+    let inactive = User::builder("another@example.com", "another").active(false).sign_in_count(0).build();
+    println!("{inactive:?}");
+    for (email, username) in [("not-an-email", "bob"), ("bob@example.com", "  "), ("bob@example.com", &"b".repeat(40))] {
+        if let Err(error) = User::new(email, username) {
+            println!("error: {error}");
+        }
+    }
+
+    // 用户仓库
+    let mut store = UserStore::new();
+    for (email, username) in [("someone@example.com", "someone"), ("SOMEONE@example.com", "other"), ("x@example.com", "Someone")] {
+        let created = User::new(email, username).map_err(|error| error.to_string()).and_then(|user| {
+            store.create(user).map(|user| user.username.to_string()).map_err(|error| error.to_string())
+        });
+        println!("create {username} <{email}>: {created:?}");
+    }
+    println!("sign in: {:?}", store.sign_in("someone"));
+    println!("sign in by email: {:?}", store.sign_in("Someone@Example.com"));
+    println!("deactivate: {:?}", store.deactivate("someone"));
+    println!("sign in after deactivation: {:?}", store.sign_in("someone"));
+    println!("sign in unknown: {:?}", store.sign_in("nobody"));
+    println!("{:?}", store.find_by_email("someone@example.com"));
+
+    // 用户保存成 JSON lines，旧格式的行缺少的字段用默认值补上
+    let path = std::env::temp_dir().join("structs_users.jsonl");
+    match jsonl::save(&path, store.iter()) {
+        Ok(()) => println!("saved {} users to {}", store.len(), path.display()),
+        Err(error) => println!("failed to save {}: {error}", path.display()),
+    }
+    match jsonl::load(&path) {
+        Ok(file) => println!("loaded back: {:?}", file.users),
+        Err(error) => println!("failed to load {}: {error}", path.display()),
+    }
+    let old_format = r#"{"username":"veteran","email":"veteran@example.com"}
+{"username":"broken","email":
+{"username":"quote\"d","email":"q@example.com","active":false,"sign_in_count":7,"nickname":"q"}
+{"username":"nobody","email":"not-an-email"}"#;
+    let file = jsonl::parse(old_format);
+    for user in &file.users {
+        println!("{}", jsonl::to_line(user));
+    }
+    for (line, error) in &file.errors {
+        println!("line {line}: {error}");
+    }
+
+    // 颜色和三维点
+    let orange: Color = "#FF8800".parse().expect("valid hex color");
+    let hsl = orange.to_hsl();
+    println!("{orange} = hsl({:.0}, {:.2}, {:.2}) -> {}", hsl.hue, hsl.saturation, hsl.lightness, Color::from_hsl(hsl));
+    println!("sky blue: {}", Color::from_hsl(Hsl { hue: 200.0, saturation: 0.8, lightness: 0.6 }));
+    println!("halfway to white: {}", orange.blend(&Color::WHITE, 0.5));
+    let bright = orange + Color(100, 100, 100);
+    println!("{bright:?} clamped to {:?} ({bright})", bright.clamped());
+    for input in ["FF8800", "#FF88", "#GG8800"] {
+        if let Err(error) = input.parse::<Color>() {
+            println!("error: {error}");
+        }
+    }
+
+    let a = Point(1, 0, 0);
+    let b = Point(0, 1, 0);
+    println!("{:?} + {:?} = {:?}, a - b = {:?}", a, b, a + b, a - b);
+    println!("a · b = {}, a × b = {:?}", a.dot(&b), a.cross(&b));
+    println!("distance from {:?} to {:?}: {:.3}", Point::ORIGIN, Point(1, 2, 2), Point::ORIGIN.distance(&Point(1, 2, 2)));
 }
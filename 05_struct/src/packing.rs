@@ -0,0 +1,201 @@
+use crate::rectangle::Rectangle;
+
+// 货架（shelf）装箱：按高度从高到低摆放，每一排是一个货架，
+// 放不进已有货架时在下面开一个新货架
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub index: usize,
+    pub rect: Rectangle,
+    pub rotated: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Packing {
+    pub placements: Vec<Placement>,
+    pub unplaced: Vec<usize>,
+}
+
+struct Shelf {
+    y: u64,
+    height: u64,
+    used: u64,
+}
+
+// index 对应 items 中的下标，rect 是放进 container 之后的位置（已加上 container 的原点）
+pub fn pack(container: &Rectangle, items: &[Rectangle], allow_rotation: bool) -> Packing {
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    // 允许旋转时按较短边排序，因为放置时会优先让矮的一边做高
+    let sort_height = |item: &Rectangle| {
+        if allow_rotation {
+            item.width.min(item.height)
+        } else {
+            item.height
+        }
+    };
+    order.sort_by_key(|&index| std::cmp::Reverse(sort_height(&items[index])));
+
+    let width = u64::from(container.width);
+    let height = u64::from(container.height);
+    let mut shelves: Vec<Shelf> = Vec::new();
+    let mut packing = Packing::default();
+    // 偏移量不超过 container 的宽高，但 container 的右边或下边可以超出 i32，
+    // 这时左上角放不进 i32 的位置不能用，放不下的物品记进 unplaced
+    let position = |shelf_y: u64, used: u64| {
+        let x = i32::try_from(container.left() + used as i64).ok()?;
+        let y = i32::try_from(container.top() + shelf_y as i64).ok()?;
+        Some((x, y))
+    };
+
+    for index in order {
+        let item = items[index];
+        let mut orientations = vec![(item, false)];
+        if allow_rotation && item.width != item.height {
+            orientations.push((item.rotated(), true));
+            // 先试矮的摆法，货架会更省高度
+            orientations.sort_by_key(|(candidate, _)| candidate.height);
+        }
+        let orientations: Vec<(Rectangle, bool)> = orientations
+            .into_iter()
+            .filter(|(candidate, _)| container.can_hold_or_equal(candidate))
+            .collect();
+
+        // 已有货架里挑剩余高度最少的那个
+        let best = shelves
+            .iter()
+            .enumerate()
+            .flat_map(|(shelf_index, shelf)| {
+                orientations.iter().filter_map(move |&(candidate, rotated)| {
+                    let fits = u64::from(candidate.height) <= shelf.height
+                        && shelf.used + u64::from(candidate.width) <= width;
+                    if !fits {
+                        return None;
+                    }
+                    let (x, y) = position(shelf.y, shelf.used)?;
+                    Some((shelf.height - u64::from(candidate.height), shelf_index, x, y, candidate, rotated))
+                })
+            })
+            .min_by_key(|(waste, ..)| *waste);
+
+        let (shelf_index, x, y, candidate, rotated) = match best {
+            Some((_, shelf_index, x, y, candidate, rotated)) => (shelf_index, x, y, candidate, rotated),
+            None => {
+                let next_y = shelves.last().map_or(0, |shelf| shelf.y + shelf.height);
+                let fresh = orientations
+                    .iter()
+                    .find(|(candidate, _)| next_y + u64::from(candidate.height) <= height);
+                match (fresh, position(next_y, 0)) {
+                    (Some(&(candidate, rotated)), Some((x, y))) => {
+                        shelves.push(Shelf { y: next_y, height: u64::from(candidate.height), used: 0 });
+                        (shelves.len() - 1, x, y, candidate, rotated)
+                    }
+                    _ => {
+                        packing.unplaced.push(index);
+                        continue;
+                    }
+                }
+            }
+        };
+
+        shelves[shelf_index].used += u64::from(candidate.width);
+        packing.placements.push(Placement {
+            index,
+            rect: Rectangle::at(x, y, candidate.width, candidate.height),
+            rotated,
+        });
+    }
+
+    packing.placements.sort_by_key(|placement| placement.index);
+    packing.unplaced.sort();
+    packing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placed(packing: &Packing) -> Vec<(usize, Rectangle, bool)> {
+        packing.placements.iter().map(|p| (p.index, p.rect, p.rotated)).collect()
+    }
+
+    #[test]
+    fn fills_shelves_left_to_right_then_top_to_bottom() {
+        let container = Rectangle::at(-20, 5, 100, 100);
+        let items = [Rectangle::new(50, 50); 4];
+        let packing = pack(&container, &items, false);
+        assert_eq!(
+            placed(&packing),
+            vec![
+                (0, Rectangle::at(-20, 5, 50, 50), false),
+                (1, Rectangle::at(30, 5, 50, 50), false),
+                (2, Rectangle::at(-20, 55, 50, 50), false),
+                (3, Rectangle::at(30, 55, 50, 50), false),
+            ]
+        );
+        assert!(packing.unplaced.is_empty());
+    }
+
+    #[test]
+    fn reuses_the_shelf_with_the_least_spare_height() {
+        let container = Rectangle::new(50, 100);
+        let items = [Rectangle::new(10, 15), Rectangle::new(40, 30), Rectangle::new(40, 20)];
+        let packing = pack(&container, &items, false);
+        assert_eq!(
+            placed(&packing),
+            vec![
+                (0, Rectangle::at(40, 30, 10, 15), false),
+                (1, Rectangle::at(0, 0, 40, 30), false),
+                (2, Rectangle::at(0, 30, 40, 20), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn rotates_items_only_when_allowed() {
+        let container = Rectangle::new(100, 20);
+        let items = [Rectangle::new(10, 50)];
+        let fixed = pack(&container, &items, false);
+        assert!(fixed.placements.is_empty());
+        assert_eq!(fixed.unplaced, vec![0]);
+
+        let rotated = pack(&container, &items, true);
+        assert_eq!(placed(&rotated), vec![(0, Rectangle::at(0, 0, 50, 10), true)]);
+        assert!(rotated.unplaced.is_empty());
+    }
+
+    #[test]
+    fn rotation_lays_items_flat_to_save_height() {
+        let container = Rectangle::new(120, 100);
+        let items = [Rectangle::new(20, 60), Rectangle::new(60, 20)];
+        let packing = pack(&container, &items, true);
+        assert_eq!(
+            placed(&packing),
+            vec![(0, Rectangle::at(0, 0, 60, 20), true), (1, Rectangle::at(60, 0, 60, 20), false)]
+        );
+        assert!(packing.unplaced.is_empty());
+    }
+
+    #[test]
+    fn reports_items_that_do_not_fit() {
+        let container = Rectangle::new(100, 100);
+        let items = [Rectangle::new(101, 1), Rectangle::new(100, 60), Rectangle::new(100, 60), Rectangle::new(100, 40)];
+        let packing = pack(&container, &items, true);
+        assert_eq!(
+            placed(&packing),
+            vec![(1, Rectangle::at(0, 0, 100, 60), false), (3, Rectangle::at(0, 60, 100, 40), false)]
+        );
+        assert_eq!(packing.unplaced, vec![0, 2]);
+    }
+
+    #[test]
+    fn positions_past_i32_are_unplaced_instead_of_wrapping() {
+        let wide = Rectangle::at(i32::MAX - 5, 0, 100, 10);
+        let packing = pack(&wide, &[Rectangle::new(10, 10), Rectangle::new(10, 10)], false);
+        assert_eq!(placed(&packing), vec![(0, Rectangle::at(i32::MAX - 5, 0, 10, 10), false)]);
+        assert_eq!(packing.unplaced, vec![1]);
+
+        let tall = Rectangle::at(0, i32::MAX - 5, 10, 100);
+        let packing = pack(&tall, &[Rectangle::new(10, 10), Rectangle::new(10, 10)], false);
+        assert_eq!(placed(&packing), vec![(0, Rectangle::at(0, i32::MAX - 5, 10, 10), false)]);
+        assert_eq!(packing.unplaced, vec![1]);
+    }
+}
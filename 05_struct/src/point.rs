@@ -0,0 +1,154 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+// 三维整数向量，和教程里的 Point(i32, i32, i32) 一样是元组结构体；
+// 和 Color 一样，运算符和 cross 的结果超出 i32 时饱和到 i32::MIN 或 i32::MAX，不会 panic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point(pub i32, pub i32, pub i32);
+
+impl Point {
+    pub const ORIGIN: Point = Point(0, 0, 0);
+
+    // 每个乘积最多 2^62，三个加起来会超出 i64，所以用 i128
+    pub fn dot(&self, other: &Point) -> i128 {
+        i128::from(self.0) * i128::from(other.0)
+            + i128::from(self.1) * i128::from(other.1)
+            + i128::from(self.2) * i128::from(other.2)
+    }
+
+    pub fn cross(&self, other: &Point) -> Point {
+        // 两个 i32 乘积之差一定放得进 i64，最后再饱和到 i32
+        let component = |a: i32, b: i32, c: i32, d: i32| {
+            saturate(i64::from(a) * i64::from(b) - i64::from(c) * i64::from(d))
+        };
+        Point(
+            component(self.1, other.2, self.2, other.1),
+            component(self.2, other.0, self.0, other.2),
+            component(self.0, other.1, self.1, other.0),
+        )
+    }
+
+    pub fn length(&self) -> f64 {
+        (self.dot(self) as f64).sqrt()
+    }
+
+    // 分量之差最多 2^32，平方和放得进 i128
+    pub fn distance(&self, other: &Point) -> f64 {
+        let square = |a: i32, b: i32| {
+            let delta = i128::from(a) - i128::from(b);
+            delta * delta
+        };
+        let sum = square(self.0, other.0) + square(self.1, other.1) + square(self.2, other.2);
+        (sum as f64).sqrt()
+    }
+}
+
+fn saturate(value: i64) -> i32 {
+    value.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point(self.0.saturating_add(other.0), self.1.saturating_add(other.1), self.2.saturating_add(other.2))
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point(self.0.saturating_sub(other.0), self.1.saturating_sub(other.1), self.2.saturating_sub(other.2))
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+// -i32::MIN 放不下，饱和成 i32::MAX
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point(self.0.saturating_neg(), self.1.saturating_neg(), self.2.saturating_neg())
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i32) -> Point {
+        Point(self.0.saturating_mul(factor), self.1.saturating_mul(factor), self.2.saturating_mul(factor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators_work_component_wise() {
+        let a = Point(1, 2, 3);
+        let b = Point(4, -5, 6);
+        assert_eq!(a + b, Point(5, -3, 9));
+        assert_eq!(a - b, Point(-3, 7, -3));
+        assert_eq!(-a, Point(-1, -2, -3));
+        assert_eq!(a * 3, Point(3, 6, 9));
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn cross_is_orthogonal_to_both_inputs() {
+        let x = Point(1, 0, 0);
+        let y = Point(0, 1, 0);
+        assert_eq!(x.cross(&y), Point(0, 0, 1));
+        assert_eq!(y.cross(&x), Point(0, 0, -1));
+        assert_eq!(x.cross(&x), Point::ORIGIN);
+
+        let pairs = [(Point(3, -7, 2), Point(5, 1, -4)), (Point(-12, 40, 9), Point(8, 8, -31)), (Point(1, 2, 3), Point(2, 4, 6))];
+        for (a, b) in pairs {
+            let normal = a.cross(&b);
+            assert_eq!(normal.dot(&a), 0, "{a:?} x {b:?}");
+            assert_eq!(normal.dot(&b), 0, "{a:?} x {b:?}");
+        }
+    }
+
+    #[test]
+    fn dot_and_length() {
+        assert_eq!(Point(1, 2, 3).dot(&Point(4, -5, 6)), 12);
+        assert_eq!(Point(1, 2, 2).length(), 3.0);
+        assert_eq!(Point(1, 2, 2).distance(&Point::ORIGIN), 3.0);
+        assert_eq!(Point(4, 6, 2).distance(&Point(1, 2, 2)), 5.0);
+    }
+
+    #[test]
+    fn extremes_do_not_overflow() {
+        let min = Point(i32::MIN, i32::MIN, i32::MIN);
+        let max = Point(i32::MAX, i32::MAX, i32::MAX);
+        assert_eq!(min.dot(&min), 3 * (1i128 << 62));
+        assert_eq!(min.length(), (3.0f64).sqrt() * 2f64.powi(31));
+
+        let far = Point(i32::MIN, 0, 0).distance(&Point(i32::MAX, 0, 0));
+        assert_eq!(far, u32::MAX as f64);
+
+        assert_eq!(max + Point(1, 1, 1), max);
+        assert_eq!(min - Point(1, 1, 1), min);
+        assert_eq!(-min, max);
+        assert_eq!(max * 2, max);
+        assert_eq!(max * -2, min);
+        assert_eq!(Point(i32::MAX, 0, 0).cross(&Point(0, i32::MAX, 0)), Point(0, 0, i32::MAX));
+        assert_eq!(Point(i32::MIN, 0, 0).cross(&Point(0, i32::MAX, 0)), Point(0, 0, i32::MIN));
+    }
+}
@@ -0,0 +1,379 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// 带位置的矩形：(x, y) 是左上角，覆盖 [x, x + width) × [y, y + height)
+// 相邻矩形共用一条边时不算相交，宽或高为 0 的矩形是空的
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rectangle {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rectangle {
+    pub fn new(width: u32, height: u32) -> Rectangle {
+        Rectangle::at(0, 0, width, height)
+    }
+
+    pub fn at(x: i32, y: i32, width: u32, height: u32) -> Rectangle {
+        Rectangle { x, y, width, height }
+    }
+
+    // u32 相乘可能溢出，放宽到 u64 后一定放得下
+    pub fn area(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+
+    pub fn perimeter(&self) -> u64 {
+        2 * (u64::from(self.width) + u64::from(self.height))
+    }
+
+    // 宽高按倍数放大，位置不变；结果超出 u32 时返回 None
+    pub fn scale(&self, factor: u32) -> Option<Rectangle> {
+        Some(Rectangle {
+            width: self.width.checked_mul(factor)?,
+            height: self.height.checked_mul(factor)?,
+            ..*self
+        })
+    }
+
+    // 宽 / 高，高为 0 时没有意义，返回 None
+    pub fn aspect_ratio(&self) -> Option<f64> {
+        if self.height == 0 {
+            return None;
+        }
+        Some(f64::from(self.width) / f64::from(self.height))
+    }
+
+    // 只比较尺寸，不看位置；要求严格大于
+    pub fn can_hold(&self, other: &Rectangle) -> bool {
+        self.width > other.width && self.height > other.height
+    }
+
+    // 非严格版本：尺寸相同也能放下
+    pub fn can_hold_or_equal(&self, other: &Rectangle) -> bool {
+        self.width >= other.width && self.height >= other.height
+    }
+
+    // 旋转 90 度：交换宽和高，位置不变
+    pub fn rotated(&self) -> Rectangle {
+        Rectangle { width: self.height, height: self.width, ..*self }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub fn left(&self) -> i64 {
+        i64::from(self.x)
+    }
+
+    pub fn top(&self) -> i64 {
+        i64::from(self.y)
+    }
+
+    pub fn right(&self) -> i64 {
+        i64::from(self.x) + i64::from(self.width)
+    }
+
+    pub fn bottom(&self) -> i64 {
+        i64::from(self.y) + i64::from(self.height)
+    }
+
+    pub fn contains_point(&self, x: i32, y: i32) -> bool {
+        let (x, y) = (i64::from(x), i64::from(y));
+        self.left() <= x && x < self.right() && self.top() <= y && y < self.bottom()
+    }
+
+    // 按位置判断 other 是否完全落在 self 里面，空矩形不包含也不被包含
+    pub fn contains(&self, other: &Rectangle) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.left() <= other.left()
+            && other.right() <= self.right()
+            && self.top() <= other.top()
+            && other.bottom() <= self.bottom()
+    }
+
+    pub fn intersects(&self, other: &Rectangle) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        let left = self.left().max(other.left());
+        let top = self.top().max(other.top());
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        if left >= right || top >= bottom {
+            return None;
+        }
+        // 交集一定落在两个矩形内部，坐标和尺寸都不会溢出
+        Some(Rectangle::at(left as i32, top as i32, (right - left) as u32, (bottom - top) as u32))
+    }
+
+    // 同时覆盖两个矩形的最小矩形；空矩形会被忽略，尺寸超出 u32 时返回 None
+    pub fn union_bounds(&self, other: &Rectangle) -> Option<Rectangle> {
+        if other.is_empty() {
+            return Some(*self);
+        }
+        if self.is_empty() {
+            return Some(*other);
+        }
+        let left = self.left().min(other.left());
+        let top = self.top().min(other.top());
+        let width = u32::try_from(self.right().max(other.right()) - left).ok()?;
+        let height = u32::try_from(self.bottom().max(other.bottom()) - top).ok()?;
+        Some(Rectangle::at(left as i32, top as i32, width, height))
+    }
+}
+
+// 文本格式：30x50 表示原点处的矩形，30x50@10,-20 带上左上角坐标；
+// 解析时宽高必须大于 0，所以非空的 Rectangle 输出后都能原样解析回来
+impl fmt::Display for Rectangle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)?;
+        if self.x != 0 || self.y != 0 {
+            write!(f, "@{},{}", self.x, self.y)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRectangleError {
+    Malformed(String),
+    InvalidNumber { field: &'static str, value: String },
+    Negative { field: &'static str, value: i64 },
+    Zero { field: &'static str },
+    TooLarge { field: &'static str, value: String },
+}
+
+impl fmt::Display for ParseRectangleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseRectangleError::Malformed(input) => {
+                write!(f, "'{input}' is not a rectangle, expected WIDTHxHEIGHT such as 30x50 or 30x50@10,20")
+            }
+            ParseRectangleError::InvalidNumber { field, value } => write!(f, "{field} '{value}' is not a number"),
+            ParseRectangleError::Negative { field, value } => write!(f, "{field} must not be negative, got {value}"),
+            ParseRectangleError::Zero { field } => write!(f, "{field} must be greater than 0"),
+            ParseRectangleError::TooLarge { field, value } => write!(f, "{field} {value} is too large"),
+        }
+    }
+}
+
+impl Error for ParseRectangleError {}
+
+impl FromStr for Rectangle {
+    type Err = ParseRectangleError;
+
+    fn from_str(s: &str) -> Result<Rectangle, ParseRectangleError> {
+        let malformed = || ParseRectangleError::Malformed(s.trim().to_string());
+        let (size, position) = match s.split_once('@') {
+            Some((size, position)) => (size, Some(position)),
+            None => (s, None),
+        };
+
+        let (width, height) = size.split_once(['x', 'X']).ok_or_else(malformed)?;
+        let width = parse_size("width", width)?;
+        let height = parse_size("height", height)?;
+
+        let (x, y) = match position {
+            Some(position) => {
+                let (x, y) = position.split_once(',').ok_or_else(malformed)?;
+                (parse_coordinate("x", x)?, parse_coordinate("y", y)?)
+            }
+            None => (0, 0),
+        };
+        Ok(Rectangle::at(x, y, width, height))
+    }
+}
+
+fn parse_size(field: &'static str, text: &str) -> Result<u32, ParseRectangleError> {
+    let text = text.trim();
+    let value: i64 = text.parse().map_err(|_| number_error(field, text))?;
+    match value {
+        value if value < 0 => Err(ParseRectangleError::Negative { field, value }),
+        0 => Err(ParseRectangleError::Zero { field }),
+        value => u32::try_from(value)
+            .map_err(|_| ParseRectangleError::TooLarge { field, value: text.to_string() }),
+    }
+}
+
+fn parse_coordinate(field: &'static str, text: &str) -> Result<i32, ParseRectangleError> {
+    let text = text.trim();
+    let value: i64 = text.parse().map_err(|_| number_error(field, text))?;
+    i32::try_from(value).map_err(|_| ParseRectangleError::TooLarge { field, value: text.to_string() })
+}
+
+// 纯数字但超出 i64 的输入也算太大，而不是“不是数字”
+fn number_error(field: &'static str, text: &str) -> ParseRectangleError {
+    let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+    if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+        ParseRectangleError::TooLarge { field, value: text.to_string() }
+    } else {
+        ParseRectangleError::InvalidNumber { field, value: text.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rectangles_sharing_an_edge_do_not_intersect() {
+        let a = Rectangle::at(0, 0, 30, 50);
+        let right = Rectangle::at(30, 0, 10, 10);
+        let below = Rectangle::at(0, 50, 10, 10);
+        let corner = Rectangle::at(30, 50, 10, 10);
+        for other in [right, below, corner] {
+            assert!(!a.intersects(&other), "{a} vs {other}");
+            assert!(!other.intersects(&a), "{other} vs {a}");
+            assert_eq!(a.intersection(&other), None);
+        }
+    }
+
+    #[test]
+    fn overlapping_by_one_unit_intersects() {
+        let a = Rectangle::at(0, 0, 30, 50);
+        let b = Rectangle::at(29, 49, 10, 10);
+        assert_eq!(a.intersection(&b), Some(Rectangle::at(29, 49, 1, 1)));
+        assert_eq!(b.intersection(&a), Some(Rectangle::at(29, 49, 1, 1)));
+    }
+
+    #[test]
+    fn empty_rectangles_never_intersect() {
+        let a = Rectangle::at(0, 0, 30, 50);
+        let zero_width = Rectangle::at(10, 10, 0, 10);
+        let zero_height = Rectangle::at(10, 10, 10, 0);
+        for empty in [zero_width, zero_height] {
+            assert!(empty.is_empty());
+            assert_eq!(a.intersection(&empty), None);
+            assert_eq!(empty.intersection(&a), None);
+            assert_eq!(empty.intersection(&empty), None);
+        }
+    }
+
+    #[test]
+    fn empty_rectangles_are_not_contained_and_contain_nothing() {
+        let a = Rectangle::at(0, 0, 30, 50);
+        let zero_width = Rectangle::at(10, 10, 0, 10);
+        let zero_height = Rectangle::at(10, 10, 10, 0);
+        for empty in [zero_width, zero_height] {
+            assert!(!a.contains(&empty));
+            assert!(!empty.contains(&a));
+            assert!(!empty.contains(&empty));
+        }
+        assert!(a.contains(&a));
+        assert!(a.contains(&Rectangle::at(20, 40, 10, 10)));
+        assert!(!a.contains(&Rectangle::at(21, 40, 10, 10)));
+    }
+
+    #[test]
+    fn empty_rectangles_contain_no_points() {
+        assert!(!Rectangle::at(10, 10, 0, 10).contains_point(10, 10));
+        assert!(!Rectangle::at(10, 10, 10, 0).contains_point(10, 10));
+        assert!(!Rectangle::new(0, 0).contains_point(0, 0));
+    }
+
+    #[test]
+    fn contains_point_includes_top_left_and_excludes_bottom_right_edges() {
+        let a = Rectangle::at(-5, -5, 10, 10);
+        assert!(a.contains_point(-5, -5));
+        assert!(a.contains_point(4, 4));
+        assert!(!a.contains_point(5, 0));
+        assert!(!a.contains_point(0, 5));
+        assert!(!a.contains_point(-6, 0));
+    }
+
+    #[test]
+    fn union_bounds_ignores_empty_rectangles() {
+        let a = Rectangle::at(0, 0, 30, 50);
+        let far_empty = Rectangle::at(1000, 1000, 0, 10);
+        assert_eq!(a.union_bounds(&far_empty), Some(a));
+        assert_eq!(far_empty.union_bounds(&a), Some(a));
+        assert_eq!(a.union_bounds(&Rectangle::at(30, 0, 10, 10)), Some(Rectangle::at(0, 0, 40, 50)));
+    }
+
+    #[test]
+    fn union_bounds_too_large_for_u32_is_none() {
+        let a = Rectangle::at(i32::MIN, 0, 1, 1);
+        let b = Rectangle::at(i32::MAX, 0, u32::MAX, 1);
+        assert_eq!(a.union_bounds(&b), None);
+    }
+
+    #[test]
+    fn can_hold_is_strict_and_can_hold_or_equal_is_not() {
+        let a = Rectangle::new(30, 50);
+        let same = Rectangle::new(30, 50);
+        let narrower = Rectangle::new(29, 49);
+        let same_width = Rectangle::new(30, 10);
+        assert!(!a.can_hold(&same));
+        assert!(a.can_hold_or_equal(&same));
+        assert!(a.can_hold(&narrower));
+        assert!(a.can_hold_or_equal(&narrower));
+        assert!(!a.can_hold(&same_width));
+        assert!(a.can_hold_or_equal(&same_width));
+        assert!(!narrower.can_hold_or_equal(&a));
+    }
+
+    #[test]
+    fn parse_rejects_zero_sizes() {
+        assert_eq!("0x7@3,4".parse::<Rectangle>(), Err(ParseRectangleError::Zero { field: "width" }));
+        assert_eq!("7x0".parse::<Rectangle>(), Err(ParseRectangleError::Zero { field: "height" }));
+        assert_eq!("0x0".parse::<Rectangle>(), Err(ParseRectangleError::Zero { field: "width" }));
+        assert_eq!(ParseRectangleError::Zero { field: "width" }.to_string(), "width must be greater than 0");
+    }
+
+    #[test]
+    fn parse_rejects_bad_input() {
+        assert!(matches!("30by50".parse::<Rectangle>(), Err(ParseRectangleError::Malformed(_))));
+        assert!(matches!("30xabc".parse::<Rectangle>(), Err(ParseRectangleError::InvalidNumber { field: "height", .. })));
+        assert!(matches!("-3x5".parse::<Rectangle>(), Err(ParseRectangleError::Negative { field: "width", value: -3 })));
+        assert!(matches!("4294967296x1".parse::<Rectangle>(), Err(ParseRectangleError::TooLarge { field: "width", .. })));
+        assert!(matches!("1x1@2147483648,0".parse::<Rectangle>(), Err(ParseRectangleError::TooLarge { field: "x", .. })));
+    }
+
+    // 简单的 xorshift 伪随机数，固定种子，失败时可以复现
+    struct Numbers(u64);
+
+    impl Numbers {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    // 空矩形解析时会报 Zero，所以只检查宽高至少为 1 的矩形
+    #[test]
+    fn display_then_parse_round_trips() {
+        let sizes = [1, 2, 30, u32::MAX - 1, u32::MAX];
+        let coordinates = [0, 1, -1, 10, -20, i32::MIN, i32::MIN + 1, i32::MAX - 1, i32::MAX];
+        let mut rects = Vec::new();
+        for &width in &sizes {
+            for &height in &sizes {
+                for &x in &coordinates {
+                    for &y in &coordinates {
+                        rects.push(Rectangle::at(x, y, width, height));
+                    }
+                }
+            }
+        }
+        let mut numbers = Numbers(0x2545_f491_4f6c_dd1d);
+        for _ in 0..10_000 {
+            let bits = numbers.next();
+            let more = numbers.next();
+            let (width, height) = ((more as u32).max(1), ((more >> 32) as u32).max(1));
+            rects.push(Rectangle::at(bits as i32, (bits >> 32) as i32, width, height));
+        }
+
+        for rect in rects {
+            let text = rect.to_string();
+            assert_eq!(text.parse::<Rectangle>(), Ok(rect), "{text}");
+        }
+    }
+}
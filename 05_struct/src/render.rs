@@ -0,0 +1,174 @@
+use std::error::Error;
+use std::fmt::{self, Write};
+
+use crate::rectangle::Rectangle;
+
+const LABELS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const COLORS: &[&str] = &["steelblue", "seagreen", "darkorange", "mediumpurple", "goldenrod", "teal"];
+
+// 字符网格每一边最多的格子数，超过时应该换一个更大的 scale
+pub const MAX_GRID_SIDE: i64 = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderError {
+    TooLarge { columns: i64, rows: i64, max: i64 },
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RenderError::TooLarge { columns, rows, max } => write!(
+                f,
+                "the grid would be {columns}x{rows} characters, at most {max}x{max} is allowed, try a larger scale"
+            ),
+        }
+    }
+}
+
+impl Error for RenderError {}
+
+// 把矩形画到字符网格上：每个字符代表 scale × scale 个单位，
+// 被矩形覆盖的格子用字母 A、B、C… 表示（几个矩形只是共用一个格子时取最前面的那个），
+// 格子里真的有两个矩形的交集时用 #，空白用 .
+pub fn render_ascii(rects: &[Rectangle], scale: u32) -> Result<String, RenderError> {
+    let scale = i64::from(scale.max(1));
+    let (left, top, right, bottom) = match extent(rects) {
+        Some(extent) => extent,
+        None => return Ok(String::new()),
+    };
+
+    let columns = (right - left + scale - 1) / scale;
+    let rows = (bottom - top + scale - 1) / scale;
+    if columns > MAX_GRID_SIDE || rows > MAX_GRID_SIDE {
+        return Err(RenderError::TooLarge { columns, rows, max: MAX_GRID_SIDE });
+    }
+
+    let overlaps = overlaps(rects);
+    let mut output = String::new();
+    for row in 0..rows {
+        let cell_top = top + row * scale;
+        for column in 0..columns {
+            let cell_left = left + column * scale;
+            // 格子可能超出 i32 的范围，直接用 i64 边界比较
+            let in_cell = |rect: &Rectangle| {
+                !rect.is_empty()
+                    && rect.left() < cell_left + scale
+                    && cell_left < rect.right()
+                    && rect.top() < cell_top + scale
+                    && cell_top < rect.bottom()
+            };
+            output.push(if overlaps.iter().any(in_cell) {
+                '#'
+            } else {
+                match rects.iter().position(in_cell) {
+                    Some(index) => LABELS[index % LABELS.len()] as char,
+                    None => '.',
+                }
+            });
+        }
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+// 两两之间真正重叠的部分，只共用一条边的矩形没有交集
+fn overlaps(rects: &[Rectangle]) -> Vec<Rectangle> {
+    let mut overlaps = Vec::new();
+    for (index, a) in rects.iter().enumerate() {
+        for b in &rects[index + 1..] {
+            overlaps.extend(a.intersection(b));
+        }
+    }
+    overlaps
+}
+
+// 导出成 SVG 文本，重叠部分用红色半透明矩形标出
+pub fn render_svg(rects: &[Rectangle]) -> String {
+    let (left, top, right, bottom) = match extent(rects) {
+        Some(extent) => extent,
+        None => return String::from("<svg xmlns=\"http://www.w3.org/2000/svg\"/>\n"),
+    };
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{left} {top} {} {}\">",
+        right - left,
+        bottom - top
+    );
+    for (index, rect) in rects.iter().enumerate().filter(|(_, rect)| !rect.is_empty()) {
+        let _ = writeln!(
+            svg,
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" fill-opacity=\"0.5\" stroke=\"black\"><title>{}</title></rect>",
+            rect.x,
+            rect.y,
+            rect.width,
+            rect.height,
+            COLORS[index % COLORS.len()],
+            LABELS[index % LABELS.len()] as char
+        );
+    }
+    for overlap in overlaps(rects) {
+        let _ = writeln!(
+            svg,
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"red\" fill-opacity=\"0.6\"/>",
+            overlap.x, overlap.y, overlap.width, overlap.height
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// 所有非空矩形的外边界 (left, top, right, bottom)
+fn extent(rects: &[Rectangle]) -> Option<(i64, i64, i64, i64)> {
+    let visible = rects.iter().filter(|rect| !rect.is_empty());
+    visible.fold(None, |extent, rect| {
+        Some(match extent {
+            None => (rect.left(), rect.top(), rect.right(), rect.bottom()),
+            Some((left, top, right, bottom)) => (
+                left.min(rect.left()),
+                top.min(rect.top()),
+                right.max(rect.right()),
+                bottom.max(rect.bottom()),
+            ),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_edges_are_not_drawn_as_overlap() {
+        let rects = [Rectangle::at(0, 0, 5, 10), Rectangle::at(5, 0, 5, 10)];
+        assert_eq!(render_ascii(&rects, 10).unwrap(), "A\n");
+        assert_eq!(render_ascii(&rects, 5).unwrap(), "AB\nAB\n");
+    }
+
+    #[test]
+    fn overlap_is_drawn_only_where_the_intersection_is() {
+        let rects = [Rectangle::at(0, 0, 20, 10), Rectangle::at(10, 0, 20, 10)];
+        assert_eq!(render_ascii(&rects, 10).unwrap(), "A#B\n");
+        // 交集 [15, 20) 落在第二个格子里，第一个格子只有 A
+        let rects = [Rectangle::at(0, 0, 20, 10), Rectangle::at(15, 0, 15, 10)];
+        assert_eq!(render_ascii(&rects, 10).unwrap(), "A#B\n");
+        let rects = [Rectangle::at(0, 0, 10, 10), Rectangle::at(5, 0, 25, 10)];
+        assert_eq!(render_ascii(&rects, 10).unwrap(), "#BB\n");
+    }
+
+    #[test]
+    fn empty_input_renders_nothing() {
+        assert_eq!(render_ascii(&[], 1).unwrap(), "");
+        assert_eq!(render_ascii(&[Rectangle::new(0, 10)], 1).unwrap(), "");
+    }
+
+    #[test]
+    fn huge_grids_are_rejected() {
+        let huge = Rectangle::new(u32::MAX, u32::MAX);
+        assert!(matches!(render_ascii(&[huge], 1), Err(RenderError::TooLarge { .. })));
+        let side = MAX_GRID_SIDE as u32;
+        assert!(render_ascii(&[Rectangle::new(side, side)], 1).is_ok());
+        assert!(render_ascii(&[Rectangle::new(side + 1, 1)], 1).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::rectangle::Rectangle;
+
+// 各种图形的共同接口，可以放进 Vec<Box<dyn Shape>> 里一起计算
+pub trait Shape: fmt::Debug {
+    fn area(&self) -> f64;
+    fn perimeter(&self) -> f64;
+    // 能盖住整个图形的最小整数坐标矩形
+    fn bounding_box(&self) -> Rectangle;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub x: f64,
+    pub y: f64,
+    pub radius: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle {
+    pub a: (f64, f64),
+    pub b: (f64, f64),
+    pub c: (f64, f64),
+}
+
+// 顶点按顺序连接，最后一个顶点再连回第一个
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    pub vertices: Vec<(f64, f64)>,
+}
+
+impl Shape for Rectangle {
+    fn area(&self) -> f64 {
+        Rectangle::area(self) as f64
+    }
+
+    fn perimeter(&self) -> f64 {
+        Rectangle::perimeter(self) as f64
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        *self
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        PI * self.radius * self.radius
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * PI * self.radius
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        let r = self.radius.abs();
+        bounds(&[(self.x - r, self.y - r), (self.x + r, self.y + r)])
+    }
+}
+
+impl Shape for Triangle {
+    fn area(&self) -> f64 {
+        shoelace(&[self.a, self.b, self.c])
+    }
+
+    fn perimeter(&self) -> f64 {
+        outline(&[self.a, self.b, self.c])
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        bounds(&[self.a, self.b, self.c])
+    }
+}
+
+impl Shape for Polygon {
+    fn area(&self) -> f64 {
+        shoelace(&self.vertices)
+    }
+
+    fn perimeter(&self) -> f64 {
+        outline(&self.vertices)
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        bounds(&self.vertices)
+    }
+}
+
+pub fn total_area(shapes: &[Box<dyn Shape>]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+pub fn sort_by_area(shapes: &mut [Box<dyn Shape>]) {
+    shapes.sort_by(|a, b| a.area().total_cmp(&b.area()));
+}
+
+// 鞋带公式，顶点顺时针或逆时针都可以
+fn shoelace(vertices: &[(f64, f64)]) -> f64 {
+    if vertices.len() < 3 {
+        return 0.0;
+    }
+    let twice: f64 = edges(vertices).map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1).sum();
+    twice.abs() / 2.0
+}
+
+fn outline(vertices: &[(f64, f64)]) -> f64 {
+    if vertices.len() < 2 {
+        return 0.0;
+    }
+    edges(vertices).map(|((x1, y1), (x2, y2))| (x2 - x1).hypot(y2 - y1)).sum()
+}
+
+fn edges(vertices: &[(f64, f64)]) -> impl Iterator<Item = ((f64, f64), (f64, f64))> + '_ {
+    vertices.iter().copied().zip(vertices.iter().copied().cycle().skip(1))
+}
+
+// 向外取整；浮点数转整数时 as 会自动截断到 i32/u32 的范围内
+fn bounds(points: &[(f64, f64)]) -> Rectangle {
+    if points.is_empty() {
+        return Rectangle::new(0, 0);
+    }
+    let min_x = points.iter().map(|p| p.0).fold(f64::INFINITY, f64::min).floor();
+    let min_y = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min).floor();
+    let max_x = points.iter().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max).ceil();
+    let max_y = points.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max).ceil();
+    Rectangle::at(min_x as i32, min_y as i32, (max_x - min_x) as u32, (max_y - min_y) as u32)
+}
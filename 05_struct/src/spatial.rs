@@ -0,0 +1,312 @@
+use crate::rectangle::Rectangle;
+
+// 四叉树空间索引：每个节点最多放 MAX_ITEMS 个矩形，放满就分成四块，
+// 跨越子节点边界的矩形留在父节点；超出整棵树范围的矩形放在根节点
+const MAX_ITEMS: usize = 8;
+const MAX_DEPTH: u32 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Id(usize);
+
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    left: i64,
+    top: i64,
+    right: i64,
+    bottom: i64,
+}
+
+impl Bounds {
+    fn of(rect: &Rectangle) -> Bounds {
+        Bounds { left: rect.left(), top: rect.top(), right: rect.right(), bottom: rect.bottom() }
+    }
+
+    fn contains(&self, other: &Bounds) -> bool {
+        self.left <= other.left && other.right <= self.right && self.top <= other.top && other.bottom <= self.bottom
+    }
+
+    fn is_empty(&self) -> bool {
+        self.left >= self.right || self.top >= self.bottom
+    }
+
+    // 和 Rectangle::intersects 一样，面积为 0 的一方和谁都不相交
+    fn overlaps(&self, other: &Bounds) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.left < other.right && other.left < self.right && self.top < other.bottom && other.top < self.bottom
+    }
+
+    fn quadrants(&self) -> [Bounds; 4] {
+        let middle_x = self.left + (self.right - self.left) / 2;
+        let middle_y = self.top + (self.bottom - self.top) / 2;
+        [
+            Bounds { right: middle_x, bottom: middle_y, ..*self },
+            Bounds { left: middle_x, bottom: middle_y, ..*self },
+            Bounds { right: middle_x, top: middle_y, ..*self },
+            Bounds { left: middle_x, top: middle_y, ..*self },
+        ]
+    }
+}
+
+#[derive(Debug)]
+struct Node {
+    bounds: Bounds,
+    depth: u32,
+    items: Vec<Id>,
+    children: Option<Box<[Node; 4]>>,
+}
+
+impl Node {
+    fn new(bounds: Bounds, depth: u32) -> Node {
+        Node { bounds, depth, items: Vec::new(), children: None }
+    }
+
+    fn child_for(&mut self, bounds: &Bounds) -> Option<&mut Node> {
+        self.children.as_mut()?.iter_mut().find(|child| child.bounds.contains(bounds))
+    }
+
+    fn insert(&mut self, id: Id, slots: &[Option<Rectangle>]) {
+        let bounds = Bounds::of(&slots[id.0].expect("inserted id must be stored"));
+        if let Some(child) = self.child_for(&bounds) {
+            child.insert(id, slots);
+            return;
+        }
+
+        self.items.push(id);
+        if self.children.is_none() && self.items.len() > MAX_ITEMS && self.depth < MAX_DEPTH {
+            self.split(slots);
+        }
+    }
+
+    fn split(&mut self, slots: &[Option<Rectangle>]) {
+        let depth = self.depth + 1;
+        let [a, b, c, d] = self.bounds.quadrants();
+        self.children = Some(Box::new([
+            Node::new(a, depth),
+            Node::new(b, depth),
+            Node::new(c, depth),
+            Node::new(d, depth),
+        ]));
+
+        for id in std::mem::take(&mut self.items) {
+            self.insert(id, slots);
+        }
+    }
+
+    fn remove(&mut self, id: Id, bounds: &Bounds) -> bool {
+        if let Some(position) = self.items.iter().position(|&item| item == id) {
+            self.items.swap_remove(position);
+            return true;
+        }
+        match self.child_for(bounds) {
+            Some(child) => child.remove(id, bounds),
+            None => false,
+        }
+    }
+
+    fn query(&self, area: &Bounds, slots: &[Option<Rectangle>], found: &mut Vec<Id>) {
+        for &id in &self.items {
+            if let Some(rect) = &slots[id.0] {
+                if Bounds::of(rect).overlaps(area) {
+                    found.push(id);
+                }
+            }
+        }
+        if let Some(children) = &self.children {
+            for child in children.iter().filter(|child| child.bounds.overlaps(area)) {
+                child.query(area, slots, found);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct QuadTree {
+    root: Node,
+    slots: Vec<Option<Rectangle>>,
+    len: usize,
+}
+
+impl QuadTree {
+    // world 是大部分矩形所在的范围，范围外的矩形也能存，只是查询时不会被剪枝
+    pub fn new(world: Rectangle) -> QuadTree {
+        QuadTree { root: Node::new(Bounds::of(&world), 0), slots: Vec::new(), len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, id: Id) -> Option<&Rectangle> {
+        self.slots.get(id.0)?.as_ref()
+    }
+
+    pub fn insert(&mut self, rect: Rectangle) -> Id {
+        let id = Id(self.slots.len());
+        self.slots.push(Some(rect));
+        self.root.insert(id, &self.slots);
+        self.len += 1;
+        id
+    }
+
+    pub fn remove(&mut self, id: Id) -> Option<Rectangle> {
+        let rect = self.slots.get_mut(id.0)?.take()?;
+        let removed = self.root.remove(id, &Bounds::of(&rect));
+        debug_assert!(removed, "stored rectangle must be in the tree");
+        self.len -= 1;
+        Some(rect)
+    }
+
+    // 包含点 (x, y) 的矩形，和 Rectangle::contains_point 的规则一致
+    pub fn query_point(&self, x: i32, y: i32) -> Vec<Id> {
+        let point = Bounds { left: i64::from(x), top: i64::from(y), right: i64::from(x) + 1, bottom: i64::from(y) + 1 };
+        self.collect(&point)
+    }
+
+    // 和 area 相交的矩形，和 Rectangle::intersects 的规则一致
+    pub fn query_overlap(&self, area: &Rectangle) -> Vec<Id> {
+        if area.is_empty() {
+            return Vec::new();
+        }
+        self.collect(&Bounds::of(area))
+    }
+
+    fn collect(&self, area: &Bounds) -> Vec<Id> {
+        let mut found = Vec::new();
+        self.root.query(area, &self.slots, &mut found);
+        found.sort();
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 简单的 xorshift 伪随机数，固定种子，失败时可以复现
+    struct Numbers(u64);
+
+    impl Numbers {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, limit: u64) -> u64 {
+            self.next() % limit
+        }
+
+        // 大部分落在 world 里，也有一些在外面或者跨过边界
+        fn coordinate(&mut self) -> i32 {
+            self.below(1400) as i32 - 200
+        }
+
+        fn rect(&mut self) -> Rectangle {
+            let (x, y) = (self.coordinate(), self.coordinate());
+            match self.below(10) {
+                0 => Rectangle::at(x, y, 0, self.below(50) as u32),
+                1 => Rectangle::at(x, y, self.below(50) as u32, 0),
+                2 => Rectangle::at(x, y, self.below(600) as u32 + 1, self.below(600) as u32 + 1),
+                _ => Rectangle::at(x, y, self.below(30) as u32 + 1, self.below(30) as u32 + 1),
+            }
+        }
+    }
+
+    fn world() -> Rectangle {
+        Rectangle::new(1000, 1000)
+    }
+
+    fn naive_point(stored: &[(Id, Rectangle)], x: i32, y: i32) -> Vec<Id> {
+        let mut ids: Vec<Id> = stored.iter().filter(|(_, rect)| rect.contains_point(x, y)).map(|&(id, _)| id).collect();
+        ids.sort();
+        ids
+    }
+
+    fn naive_overlap(stored: &[(Id, Rectangle)], area: &Rectangle) -> Vec<Id> {
+        let mut ids: Vec<Id> = stored.iter().filter(|(_, rect)| rect.intersects(area)).map(|&(id, _)| id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn empty_rectangles_are_stored_but_never_found() {
+        let mut tree = QuadTree::new(world());
+        let thin = tree.insert(Rectangle::at(10, 10, 0, 20));
+        let flat = tree.insert(Rectangle::at(10, 10, 20, 0));
+        let solid = tree.insert(Rectangle::at(10, 10, 20, 20));
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.query_overlap(&Rectangle::at(0, 0, 100, 100)), vec![solid]);
+        assert_eq!(tree.query_point(10, 10), vec![solid]);
+        assert_eq!(tree.query_overlap(&Rectangle::at(10, 10, 0, 5)), vec![]);
+        assert_eq!(tree.remove(thin), Some(Rectangle::at(10, 10, 0, 20)));
+        assert_eq!(tree.remove(flat), Some(Rectangle::at(10, 10, 20, 0)));
+        assert_eq!(tree.len(), 1);
+    }
+
+    #[test]
+    fn rectangles_on_quadrant_edges_and_outside_the_world_are_found() {
+        let mut tree = QuadTree::new(world());
+        // 先塞满左上角，让根节点分裂
+        let filler: Vec<Id> = (0..20).map(|i| tree.insert(Rectangle::at(i * 10, 0, 5, 5))).collect();
+        assert!(tree.root.children.is_some());
+
+        let across = tree.insert(Rectangle::at(495, 495, 10, 10));
+        let outside = tree.insert(Rectangle::at(-50, 2000, 10, 10));
+        let straddling = tree.insert(Rectangle::at(990, 990, 20, 20));
+        assert_eq!(tree.query_point(500, 500), vec![across]);
+        assert_eq!(tree.query_point(499, 499), vec![across]);
+        assert_eq!(tree.query_point(-45, 2005), vec![outside]);
+        assert_eq!(tree.query_point(1005, 1005), vec![straddling]);
+        assert_eq!(tree.query_point(1000, 1000), vec![straddling]);
+        assert_eq!(tree.query_overlap(&Rectangle::at(0, 0, 11, 1)), vec![filler[0], filler[1]]);
+        assert_eq!(tree.len(), 23);
+    }
+
+    #[test]
+    fn remove_returns_the_rectangle_once() {
+        let mut tree = QuadTree::new(world());
+        let ids: Vec<Id> = (0..50).map(|i| tree.insert(Rectangle::at(i * 20, i * 20, 15, 15))).collect();
+        assert_eq!(tree.remove(ids[7]), Some(Rectangle::at(140, 140, 15, 15)));
+        assert_eq!(tree.remove(ids[7]), None);
+        assert_eq!(tree.get(ids[7]), None);
+        assert_eq!(tree.query_point(145, 145), vec![]);
+        assert_eq!(tree.get(ids[8]), Some(&Rectangle::at(160, 160, 15, 15)));
+        assert_eq!(tree.len(), 49);
+    }
+
+    #[test]
+    fn queries_match_a_naive_scan() {
+        let mut numbers = Numbers(0x9e37_79b9_7f4a_7c15);
+        let mut tree = QuadTree::new(world());
+        let mut stored = Vec::new();
+
+        for round in 0..2000 {
+            if round % 3 == 2 && !stored.is_empty() {
+                let (id, rect) = stored.swap_remove(numbers.below(stored.len() as u64) as usize);
+                assert_eq!(tree.remove(id), Some(rect));
+            } else {
+                let rect = numbers.rect();
+                stored.push((tree.insert(rect), rect));
+            }
+        }
+        assert_eq!(tree.len(), stored.len());
+        assert!(tree.root.children.is_some());
+
+        for _ in 0..500 {
+            let (x, y) = (numbers.coordinate(), numbers.coordinate());
+            assert_eq!(tree.query_point(x, y), naive_point(&stored, x, y), "point {x},{y}");
+            let area = numbers.rect();
+            assert_eq!(tree.query_overlap(&area), naive_overlap(&stored, &area), "area {area}");
+        }
+        // 四叉树分界线上的点
+        for (x, y) in [(500, 500), (499, 500), (250, 750), (0, 0), (999, 999), (1000, 1000)] {
+            assert_eq!(tree.query_point(x, y), naive_point(&stored, x, y), "point {x},{y}");
+        }
+    }
+}
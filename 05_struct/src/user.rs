@@ -0,0 +1,238 @@
+use std::error::Error;
+use std::fmt;
+
+pub mod jsonl;
+pub mod store;
+
+pub const MAX_USERNAME_LEN: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserError {
+    InvalidEmail(String),
+    EmptyUsername,
+    UsernameTooLong { length: usize, max: usize },
+    UsernameContainsAt(String),
+}
+
+impl fmt::Display for UserError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UserError::InvalidEmail(email) => write!(f, "'{email}' is not a valid email address"),
+            UserError::EmptyUsername => write!(f, "username must not be empty"),
+            UserError::UsernameTooLong { length, max } => {
+                write!(f, "username is {length} characters long, the limit is {max}")
+            }
+            UserError::UsernameContainsAt(username) => {
+                write!(f, "username '{username}' must not contain '@', it would look like an email address")
+            }
+        }
+    }
+}
+
+impl Error for UserError {}
+
+// 只能通过 new 创建，所以拿到的 Username 一定是合法的；
+// 不允许 @，登录时才能区分用户名和邮箱
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Username(String);
+
+impl Username {
+    pub fn new(username: &str) -> Result<Username, UserError> {
+        let username = username.trim();
+        let length = username.chars().count();
+        if length == 0 {
+            return Err(UserError::EmptyUsername);
+        }
+        if length > MAX_USERNAME_LEN {
+            return Err(UserError::UsernameTooLong { length, max: MAX_USERNAME_LEN });
+        }
+        if username.contains('@') {
+            return Err(UserError::UsernameContainsAt(username.to_string()));
+        }
+        Ok(Username(username.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+// 简单的格式检查：local@domain，domain 至少有一个点，且不含空白
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Email(String);
+
+impl Email {
+    pub fn new(email: &str) -> Result<Email, UserError> {
+        let email = email.trim();
+        let invalid = || UserError::InvalidEmail(email.to_string());
+
+        let (local, domain) = email.split_once('@').ok_or_else(invalid)?;
+        let valid = !local.is_empty()
+            && !domain.contains('@')
+            && !email.chars().any(char::is_whitespace)
+            && domain.contains('.')
+            && domain.split('.').all(|label| !label.is_empty());
+        if !valid {
+            return Err(invalid());
+        }
+        Ok(Email(email.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Username {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&self.0)
+    }
+}
+
+impl fmt::Display for Email {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct User {
+    pub active: bool,
+    pub username: Username,
+    pub email: Email,
+    pub sign_in_count: u64,
+}
+
+impl User {
+    // 和 build_user 一样：新用户默认是激活的，登录次数为 1
+    pub fn new(email: &str, username: &str) -> Result<User, UserError> {
+        User::builder(email, username).build()
+    }
+
+    pub fn builder(email: &str, username: &str) -> UserBuilder {
+        UserBuilder {
+            email: email.to_string(),
+            username: username.to_string(),
+            active: true,
+            sign_in_count: 1,
+        }
+    }
+}
+
+// 必填的 email 和 username 在 builder 创建时给出，可选字段用方法设置
+#[derive(Debug, Clone)]
+pub struct UserBuilder {
+    email: String,
+    username: String,
+    active: bool,
+    sign_in_count: u64,
+}
+
+impl UserBuilder {
+    pub fn active(mut self, active: bool) -> UserBuilder {
+        self.active = active;
+        self
+    }
+
+    pub fn sign_in_count(mut self, sign_in_count: u64) -> UserBuilder {
+        self.sign_in_count = sign_in_count;
+        self
+    }
+
+    pub fn build(self) -> Result<User, UserError> {
+        Ok(User {
+            active: self.active,
+            username: Username::new(&self.username)?,
+            email: Email::new(&self.email)?,
+            sign_in_count: self.sign_in_count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usernames_are_trimmed() {
+        let username = Username::new("  alice\t\n").unwrap();
+        assert_eq!(username.as_str(), "alice");
+        assert_eq!(format!("[{username:>7}]"), "[  alice]");
+    }
+
+    #[test]
+    fn blank_usernames_are_empty() {
+        assert_eq!(Username::new(""), Err(UserError::EmptyUsername));
+        assert_eq!(Username::new(" \t\n"), Err(UserError::EmptyUsername));
+    }
+
+    #[test]
+    fn username_length_counts_characters_not_bytes() {
+        let ascii = "a".repeat(MAX_USERNAME_LEN);
+        assert_eq!(Username::new(&ascii).unwrap().as_str(), ascii);
+        // 每个汉字在 UTF-8 里占 3 个字节，但只算一个字符
+        let chinese = "张".repeat(MAX_USERNAME_LEN);
+        assert_eq!(Username::new(&chinese).unwrap().as_str(), chinese);
+        let emoji = "🦀".repeat(MAX_USERNAME_LEN);
+        assert!(Username::new(&emoji).is_ok());
+
+        let too_long = "张".repeat(MAX_USERNAME_LEN + 1);
+        assert_eq!(
+            Username::new(&too_long),
+            Err(UserError::UsernameTooLong { length: MAX_USERNAME_LEN + 1, max: MAX_USERNAME_LEN })
+        );
+        // 两边的空白不计入长度
+        let padded = format!("  {ascii}  ");
+        assert!(Username::new(&padded).is_ok());
+    }
+
+    #[test]
+    fn usernames_must_not_look_like_emails() {
+        assert_eq!(
+            Username::new(" alice@example.com "),
+            Err(UserError::UsernameContainsAt(String::from("alice@example.com")))
+        );
+    }
+
+    #[test]
+    fn valid_emails_are_trimmed() {
+        assert_eq!(Email::new(" alice@example.com\n").unwrap().as_str(), "alice@example.com");
+        assert!(Email::new("a.b+tag@mail.example.co.uk").is_ok());
+    }
+
+    #[test]
+    fn invalid_emails_are_rejected() {
+        for email in [
+            "",
+            "alice",
+            "@example.com",
+            "alice@",
+            "alice@example",
+            "alice@@example.com",
+            "alice@exa@mple.com",
+            "al ice@example.com",
+            "alice@.example.com",
+            "alice@example..com",
+            "alice@example.com.",
+        ] {
+            assert_eq!(Email::new(email), Err(UserError::InvalidEmail(email.to_string())), "{email:?}");
+        }
+        assert_eq!(Email::new("  bad  "), Err(UserError::InvalidEmail(String::from("bad"))));
+    }
+
+    #[test]
+    fn builder_validates_both_fields() {
+        let user = User::builder("alice@example.com", " alice ").active(false).sign_in_count(7).build().unwrap();
+        assert_eq!(user.username.as_str(), "alice");
+        assert_eq!(user.email.as_str(), "alice@example.com");
+        assert!(!user.active);
+        assert_eq!(user.sign_in_count, 7);
+
+        let fresh = User::new("alice@example.com", "alice").unwrap();
+        assert!(fresh.active);
+        assert_eq!(fresh.sign_in_count, 1);
+
+        assert_eq!(User::new("alice@example.com", ""), Err(UserError::EmptyUsername));
+        assert_eq!(User::new("nope", "alice"), Err(UserError::InvalidEmail(String::from("nope"))));
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+use crate::user::{User, UserError};
+
+// 每个用户一行 JSON 对象，例如：
+// {"username":"someone","email":"someone@example.com","active":true,"sign_in_count":3}
+// 旧文件里缺少的字段用默认值补上：active 为 true，sign_in_count 为 0；
+// 新版本加上的、这里不认识的字段会被忽略，值可以是任何合法的 JSON
+//
+// 解析器是手写的：这个教程 crate 不带任何依赖，离线环境也能编译，
+// 而格式只有一行一个扁平对象，为它引入 serde 和 serde_json 不划算
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineError {
+    Syntax(String),
+    MissingField(&'static str),
+    WrongType { field: &'static str, expected: &'static str },
+    Invalid(UserError),
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LineError::Syntax(reason) => write!(f, "malformed JSON: {reason}"),
+            LineError::MissingField(field) => write!(f, "missing field '{field}'"),
+            LineError::WrongType { field, expected } => write!(f, "field '{field}' should be {expected}"),
+            LineError::Invalid(error) => write!(f, "invalid user: {error}"),
+        }
+    }
+}
+
+impl Error for LineError {}
+
+impl From<UserError> for LineError {
+    fn from(error: UserError) -> LineError {
+        LineError::Invalid(error)
+    }
+}
+
+// 读取结果：能解析的用户，以及出错的行号（从 1 开始）和原因
+#[derive(Debug, Clone, Default)]
+pub struct UserFile {
+    pub users: Vec<User>,
+    pub errors: Vec<(usize, LineError)>,
+}
+
+pub fn to_line(user: &User) -> String {
+    format!(
+        "{{\"username\":{},\"email\":{},\"active\":{},\"sign_in_count\":{}}}",
+        quote(user.username.as_str()),
+        quote(user.email.as_str()),
+        user.active,
+        user.sign_in_count
+    )
+}
+
+pub fn from_line(line: &str) -> Result<User, LineError> {
+    let mut fields = Parser::new(line).object()?;
+
+    let username = match fields.remove("username") {
+        Some(Value::String(username)) => username,
+        Some(_) => return Err(LineError::WrongType { field: "username", expected: "a string" }),
+        None => return Err(LineError::MissingField("username")),
+    };
+    let email = match fields.remove("email") {
+        Some(Value::String(email)) => email,
+        Some(_) => return Err(LineError::WrongType { field: "email", expected: "a string" }),
+        None => return Err(LineError::MissingField("email")),
+    };
+    let active = match fields.remove("active") {
+        Some(Value::Bool(active)) => active,
+        None | Some(Value::Null) => true,
+        Some(_) => return Err(LineError::WrongType { field: "active", expected: "true or false" }),
+    };
+    let sign_in_count = match fields.remove("sign_in_count") {
+        Some(Value::Number(count)) => count,
+        None | Some(Value::Null) => 0,
+        Some(_) => return Err(LineError::WrongType { field: "sign_in_count", expected: "a non-negative integer" }),
+    };
+
+    Ok(User::builder(&email, &username).active(active).sign_in_count(sign_in_count).build()?)
+}
+
+// 空行会被跳过，坏行记录下来后继续读后面的行
+pub fn parse(contents: &str) -> UserFile {
+    let mut file = UserFile::default();
+    for (number, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match from_line(line) {
+            Ok(user) => file.users.push(user),
+            Err(error) => file.errors.push((number + 1, error)),
+        }
+    }
+    file
+}
+
+// 文件不存在时返回空的结果
+pub fn load(path: &Path) -> io::Result<UserFile> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(parse(&contents)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(UserFile::default()),
+        Err(error) => Err(error),
+    }
+}
+
+pub fn save<'a>(path: &Path, users: impl IntoIterator<Item = &'a User>) -> io::Result<()> {
+    let mut contents = String::new();
+    for user in users {
+        contents.push_str(&to_line(user));
+        contents.push('\n');
+    }
+    fs::write(path, contents)
+}
+
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// 用户字段只会用到字符串、非负整数、true/false 和 null；
+// 其他任何合法的 JSON 值（负数、小数、数组、嵌套对象）都记成 Other，只有已知字段是 Other 时才报错
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    String(String),
+    Number(u64),
+    Bool(bool),
+    Null,
+    Other,
+}
+
+// 嵌套太深的值直接当成格式错误，免得递归把栈用完
+const MAX_DEPTH: usize = 32;
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Parser<'a> {
+        Parser { chars: text.chars().peekable() }
+    }
+
+    // 整行必须是一个对象，对象后面不能再有别的内容
+    fn object(&mut self) -> Result<HashMap<String, Value>, LineError> {
+        let fields = self.members(0)?;
+        if let Some(c) = self.peek() {
+            return Err(syntax(format!("unexpected '{c}' after the object")));
+        }
+        Ok(fields)
+    }
+
+    fn members(&mut self, depth: usize) -> Result<HashMap<String, Value>, LineError> {
+        let mut fields = HashMap::new();
+        self.expect('{')?;
+        if self.peek() == Some('}') {
+            self.chars.next();
+            return Ok(fields);
+        }
+        loop {
+            let key = self.string()?;
+            self.expect(':')?;
+            let value = self.value(depth)?;
+            if fields.insert(key.clone(), value).is_some() {
+                return Err(syntax(format!("duplicate key '{key}'")));
+            }
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(fields),
+                Some(c) => return Err(syntax(format!("expected ',' or '}}', found '{c}'"))),
+                None => return Err(syntax("unexpected end of line")),
+            }
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, LineError> {
+        if depth >= MAX_DEPTH {
+            return Err(syntax("values are nested too deeply"));
+        }
+        match self.peek() {
+            Some('"') => self.string().map(Value::String),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            Some('{') => self.members(depth + 1).map(|_| Value::Other),
+            Some('[') => self.array(depth + 1),
+            Some(c) if c.is_ascii_alphabetic() => {
+                let mut word = String::new();
+                while let Some(c) = self.chars.next_if(char::is_ascii_alphabetic) {
+                    word.push(c);
+                }
+                match word.as_str() {
+                    "true" => Ok(Value::Bool(true)),
+                    "false" => Ok(Value::Bool(false)),
+                    "null" => Ok(Value::Null),
+                    _ => Err(syntax(format!("unexpected word '{word}'"))),
+                }
+            }
+            Some(c) => Err(syntax(format!("unexpected '{c}' where a value should be"))),
+            None => Err(syntax("unexpected end of line")),
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Value, LineError> {
+        self.expect('[')?;
+        if self.peek() == Some(']') {
+            self.chars.next();
+            return Ok(Value::Other);
+        }
+        loop {
+            self.value(depth)?;
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(Value::Other),
+                Some(c) => return Err(syntax(format!("expected ',' or ']', found '{c}'"))),
+                None => return Err(syntax("unexpected end of line")),
+            }
+        }
+    }
+
+    // 按 JSON 的数字语法读完整个数字；能放进 u64 的非负整数是 Number，其余是 Other
+    fn number(&mut self) -> Result<Value, LineError> {
+        let mut text = String::new();
+        if let Some(c) = self.chars.next_if_eq(&'-') {
+            text.push(c);
+        }
+        let integer = self.digits(&mut text);
+        if integer.is_empty() {
+            return Err(syntax(format!("number '{text}' has no digits")));
+        }
+        if integer.len() > 1 && integer.starts_with('0') {
+            return Err(syntax(format!("number '{integer}' has a leading zero")));
+        }
+        let mut whole = !text.starts_with('-');
+        if let Some(c) = self.chars.next_if_eq(&'.') {
+            text.push(c);
+            if self.digits(&mut text).is_empty() {
+                return Err(syntax(format!("number '{text}' has no digits after '.'")));
+            }
+            whole = false;
+        }
+        if let Some(c) = self.chars.next_if(|&c| c == 'e' || c == 'E') {
+            text.push(c);
+            if let Some(c) = self.chars.next_if(|&c| c == '+' || c == '-') {
+                text.push(c);
+            }
+            if self.digits(&mut text).is_empty() {
+                return Err(syntax(format!("number '{text}' has no digits in the exponent")));
+            }
+            whole = false;
+        }
+        match text.parse() {
+            Ok(number) if whole => Ok(Value::Number(number)),
+            _ => Ok(Value::Other),
+        }
+    }
+
+    // 读入连续的数字并追加到 text，返回这次读到的部分
+    fn digits(&mut self, text: &mut String) -> String {
+        let mut digits = String::new();
+        while let Some(c) = self.chars.next_if(char::is_ascii_digit) {
+            digits.push(c);
+        }
+        text.push_str(&digits);
+        digits
+    }
+
+    fn string(&mut self) -> Result<String, LineError> {
+        self.expect('"')?;
+        let mut text = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(text),
+                Some('\\') => text.push(self.escape()?),
+                Some(c) if c.is_control() => return Err(syntax("control character inside a string")),
+                Some(c) => text.push(c),
+                None => return Err(syntax("unterminated string")),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, LineError> {
+        match self.chars.next() {
+            Some('"') => Ok('"'),
+            Some('\\') => Ok('\\'),
+            Some('/') => Ok('/'),
+            Some('b') => Ok('\u{8}'),
+            Some('f') => Ok('\u{c}'),
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('t') => Ok('\t'),
+            Some('u') => {
+                let high = self.hex4()?;
+                // 基本平面以外的字符写成一对代理项
+                let code = if (0xD800..0xDC00).contains(&high) {
+                    if self.chars.next() != Some('\\') || self.chars.next() != Some('u') {
+                        return Err(syntax("unpaired surrogate"));
+                    }
+                    let low = self.hex4()?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err(syntax("unpaired surrogate"));
+                    }
+                    0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                } else {
+                    high
+                };
+                char::from_u32(code).ok_or_else(|| syntax("invalid \\u escape"))
+            }
+            Some(c) => Err(syntax(format!("unknown escape '\\{c}'"))),
+            None => Err(syntax("unterminated string")),
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, LineError> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self.chars.next().and_then(|c| c.to_digit(16));
+            code = code * 16 + digit.ok_or_else(|| syntax("invalid \\u escape"))?;
+        }
+        Ok(code)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), LineError> {
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(syntax(format!("expected '{expected}', found '{c}'"))),
+            None => Err(syntax(format!("expected '{expected}', found end of line"))),
+        }
+    }
+
+    // 跳过空白后的下一个字符
+    fn next(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.next()
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.peek().copied()
+    }
+
+    // JSON 只把空格、\t、\n、\r 当作空白，全角空格之类的 Unicode 空白是格式错误
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| matches!(c, ' ' | '\t' | '\n' | '\r')).is_some() {}
+    }
+}
+
+fn syntax(reason: impl Into<String>) -> LineError {
+    LineError::Syntax(reason.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn users_round_trip_through_a_line() {
+        let user = User::builder("a@example.com", "quote\"d\ttab \\ x").active(false).sign_in_count(7).build().unwrap();
+        assert_eq!(from_line(&to_line(&user)), Ok(user));
+    }
+
+    #[test]
+    fn only_json_whitespace_is_skipped() {
+        let line = " \t{ \"username\" :\t\"a\" ,\r\n\"email\":\"a@example.com\" }\r";
+        assert_eq!(from_line(line).unwrap().username.as_str(), "a");
+        for space in ['\u{a0}', '\u{3000}', '\u{2028}', '\u{b}', '\u{c}'] {
+            let line = format!("{{\"username\":\"a\",{space}\"email\":\"a@example.com\"}}");
+            assert!(matches!(from_line(&line), Err(LineError::Syntax(_))), "{space:?}");
+        }
+    }
+
+    #[test]
+    fn missing_fields_get_defaults() {
+        let user = from_line(r#"{"username":"old","email":"old@example.com"}"#).unwrap();
+        assert!(user.active);
+        assert_eq!(user.sign_in_count, 0);
+    }
+
+    #[test]
+    fn unknown_fields_of_any_type_are_ignored() {
+        let line = r#"{"username":"new","tags":["x",{"y":[1,-2.5e3]}],"score":-1,"ratio":1.5,
+            "big":123456789012345678901234567890,"profile":{"bio":null,"links":[]},"email":"new@example.com","sign_in_count":4}"#;
+        let user = from_line(&line.replace('\n', "")).unwrap();
+        assert_eq!(user.username.as_str(), "new");
+        assert_eq!(user.sign_in_count, 4);
+    }
+
+    #[test]
+    fn known_fields_with_the_wrong_type_are_errors() {
+        let line = r#"{"username":"a","email":"a@example.com","sign_in_count":-1}"#;
+        assert!(matches!(from_line(line), Err(LineError::WrongType { field: "sign_in_count", .. })));
+        let line = r#"{"username":"a","email":"a@example.com","sign_in_count":1.5}"#;
+        assert!(matches!(from_line(line), Err(LineError::WrongType { field: "sign_in_count", .. })));
+        let line = r#"{"username":["a"],"email":"a@example.com"}"#;
+        assert!(matches!(from_line(line), Err(LineError::WrongType { field: "username", .. })));
+    }
+
+    #[test]
+    fn malformed_lines_are_reported_and_skipped() {
+        let contents = [
+            r#"{"username":"a","email":"a@example.com"}"#,
+            r#"{"username":"b","email":"#,
+            "",
+            r#"{"username":"c","email":"c@example.com","x":[1,2}"#,
+            r#"{"username":"d","email":"d@example.com","x":01}"#,
+            r#"{"username":"e","email":"e@example.com"} trailing"#,
+            r#"{"username":"f","email":"f@example.com"}"#,
+        ]
+        .join("\n");
+        let file = parse(&contents);
+        let names: Vec<&str> = file.users.iter().map(|user| user.username.as_str()).collect();
+        assert_eq!(names, ["a", "f"]);
+        let lines: Vec<usize> = file.errors.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, [2, 4, 5, 6]);
+    }
+
+    #[test]
+    fn deeply_nested_values_are_rejected() {
+        let line = format!(r#"{{"username":"a","email":"a@example.com","x":{}{}}}"#, "[".repeat(100), "]".repeat(100));
+        assert!(matches!(from_line(&line), Err(LineError::Syntax(_))));
+    }
+}
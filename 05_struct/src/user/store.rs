@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::user::User;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoreError {
+    UsernameTaken(String),
+    EmailTaken(String),
+    NotFound(String),
+    Inactive(String),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StoreError::UsernameTaken(username) => write!(f, "username '{username}' is already taken"),
+            StoreError::EmailTaken(email) => write!(f, "email '{email}' is already registered"),
+            StoreError::NotFound(login) => write!(f, "no user with username or email '{login}'"),
+            StoreError::Inactive(username) => write!(f, "user '{username}' has been deactivated"),
+        }
+    }
+}
+
+impl Error for StoreError {}
+
+// 内存中的用户仓库，用户名和邮箱都不区分大小写地保持唯一
+#[derive(Debug, Default)]
+pub struct UserStore {
+    users: Vec<User>,
+    by_username: HashMap<String, usize>,
+    by_email: HashMap<String, usize>,
+}
+
+impl UserStore {
+    pub fn new() -> UserStore {
+        UserStore::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &User> {
+        self.users.iter()
+    }
+
+    pub fn create(&mut self, user: User) -> Result<&User, StoreError> {
+        let username = key(user.username.as_str());
+        let email = key(user.email.as_str());
+        if self.by_username.contains_key(&username) {
+            return Err(StoreError::UsernameTaken(user.username.to_string()));
+        }
+        if self.by_email.contains_key(&email) {
+            return Err(StoreError::EmailTaken(user.email.to_string()));
+        }
+
+        let index = self.users.len();
+        self.by_username.insert(username, index);
+        self.by_email.insert(email, index);
+        self.users.push(user);
+        Ok(&self.users[index])
+    }
+
+    pub fn find_by_username(&self, username: &str) -> Option<&User> {
+        self.by_username.get(&key(username)).map(|&index| &self.users[index])
+    }
+
+    pub fn find_by_email(&self, email: &str) -> Option<&User> {
+        self.by_email.get(&key(email)).map(|&index| &self.users[index])
+    }
+
+    pub fn deactivate(&mut self, login: &str) -> Result<(), StoreError> {
+        let index = self.index_of(login)?;
+        self.users[index].active = false;
+        Ok(())
+    }
+
+    // 登录成功时返回新的登录次数，停用的用户不能登录
+    pub fn sign_in(&mut self, login: &str) -> Result<u64, StoreError> {
+        let index = self.index_of(login)?;
+        let user = &mut self.users[index];
+        if !user.active {
+            return Err(StoreError::Inactive(user.username.to_string()));
+        }
+        user.sign_in_count = user.sign_in_count.saturating_add(1);
+        Ok(user.sign_in_count)
+    }
+
+    // login 可以是用户名，也可以是邮箱
+    fn index_of(&self, login: &str) -> Result<usize, StoreError> {
+        let login_key = key(login);
+        self.by_username
+            .get(&login_key)
+            .or_else(|| self.by_email.get(&login_key))
+            .copied()
+            .ok_or_else(|| StoreError::NotFound(login.trim().to_string()))
+    }
+}
+
+fn key(text: &str) -> String {
+    text.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user::UserError;
+
+    fn user(email: &str, username: &str) -> User {
+        User::new(email, username).unwrap()
+    }
+
+    #[test]
+    fn usernames_and_emails_are_unique_ignoring_case() {
+        let mut store = UserStore::new();
+        store.create(user("someone@example.com", "someone")).unwrap();
+        assert_eq!(
+            store.create(user("other@example.com", "SomeOne")),
+            Err(StoreError::UsernameTaken(String::from("SomeOne")))
+        );
+        assert_eq!(
+            store.create(user("SOMEONE@example.com", "other")),
+            Err(StoreError::EmailTaken(String::from("SOMEONE@example.com")))
+        );
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn usernames_cannot_look_like_emails() {
+        assert_eq!(
+            User::new("b@example.com", "a@example.com"),
+            Err(UserError::UsernameContainsAt(String::from("a@example.com")))
+        );
+    }
+
+    #[test]
+    fn lookups_find_users_by_username_or_email() {
+        let mut store = UserStore::new();
+        store.create(user("a@example.com", "alice")).unwrap();
+        store.create(user("b@example.com", "bob")).unwrap();
+        assert_eq!(store.find_by_username("BOB").unwrap().email.as_str(), "b@example.com");
+        assert_eq!(store.find_by_email("A@Example.com").unwrap().username.as_str(), "alice");
+        assert!(store.find_by_username("a@example.com").is_none());
+        assert!(store.find_by_email("alice").is_none());
+    }
+
+    #[test]
+    fn sign_in_counts_and_refuses_inactive_users() {
+        let mut store = UserStore::new();
+        store.create(user("a@example.com", "alice")).unwrap();
+        assert_eq!(store.sign_in("alice"), Ok(2));
+        assert_eq!(store.sign_in("a@example.com"), Ok(3));
+        assert_eq!(store.deactivate("alice"), Ok(()));
+        assert_eq!(store.sign_in("alice"), Err(StoreError::Inactive(String::from("alice"))));
+        assert_eq!(store.find_by_username("alice").unwrap().sign_in_count, 3);
+        assert_eq!(store.sign_in("nobody"), Err(StoreError::NotFound(String::from("nobody"))));
+        assert_eq!(store.deactivate("nobody"), Err(StoreError::NotFound(String::from("nobody"))));
+    }
+}
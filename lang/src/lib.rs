@@ -0,0 +1,73 @@
+use std::env;
+
+// 02_guessing_game、03_variables 和 n08_hashMap 共用的界面语言选择：
+// 命令行的 --lang 优先，其次看环境变量 LANG，都没有时用英文
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    En,
+    Zh,
+}
+
+impl Lang {
+    // 接受 en、zh 以及 zh_CN.UTF-8 这样的 locale 写法
+    pub fn from_tag(tag: &str) -> Option<Lang> {
+        let tag = tag.trim().to_lowercase();
+        if tag.starts_with("zh") {
+            Some(Lang::Zh)
+        } else if tag.starts_with("en") || tag == "c" || tag == "posix" {
+            Some(Lang::En)
+        } else {
+            None
+        }
+    }
+
+    // 没有 --lang 时返回 Ok(None)；--lang 后面缺少值或者值不认识时返回 Err，调用方应当报错退出
+    pub fn from_args(args: &[String]) -> Result<Option<Lang>, String> {
+        match args.iter().position(|arg| arg == "--lang") {
+            None => Ok(None),
+            Some(index) => {
+                let tag = args.get(index + 1).map(String::as_str).unwrap_or_default();
+                Lang::from_tag(tag).map(Some).ok_or_else(|| tag.to_string())
+            }
+        }
+    }
+
+    // 用来决定报错信息的语言，所以 --lang 不合法时也会退回到 LANG
+    pub fn detect(args: &[String]) -> Lang {
+        Lang::from_args(args)
+            .ok()
+            .flatten()
+            .or_else(|| env::var("LANG").ok().and_then(|tag| Lang::from_tag(&tag)))
+            .unwrap_or(Lang::En)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn tags_and_locales() {
+        assert_eq!(Lang::from_tag("zh"), Some(Lang::Zh));
+        assert_eq!(Lang::from_tag("zh_CN.UTF-8"), Some(Lang::Zh));
+        assert_eq!(Lang::from_tag(" EN "), Some(Lang::En));
+        assert_eq!(Lang::from_tag("en_US.UTF-8"), Some(Lang::En));
+        assert_eq!(Lang::from_tag("C"), Some(Lang::En));
+        assert_eq!(Lang::from_tag("POSIX"), Some(Lang::En));
+        assert_eq!(Lang::from_tag("fr"), None);
+        assert_eq!(Lang::from_tag(""), None);
+    }
+
+    #[test]
+    fn lang_flag() {
+        assert_eq!(Lang::from_args(&args(&["prog"])), Ok(None));
+        assert_eq!(Lang::from_args(&args(&["prog", "--lang", "zh"])), Ok(Some(Lang::Zh)));
+        assert_eq!(Lang::from_args(&args(&["prog", "--lang", "fr"])), Err(String::from("fr")));
+        assert_eq!(Lang::from_args(&args(&["prog", "--lang"])), Err(String::new()));
+        assert_eq!(Lang::detect(&args(&["prog", "--lang", "zh"])), Lang::Zh);
+    }
+}
@@ -0,0 +1,395 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::ip::{AddrParseError, IpAddr, IpAddrKind};
+
+// 网络地址加前缀长度，例如 10.0.0.0/8；network 的主机位总是 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Cidr {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CidrError {
+    MissingPrefix(String),
+    Address(AddrParseError),
+    InvalidPrefix(String),
+    PrefixTooLong { prefix_len: u8, max: u8 },
+    HostBitsSet { input: String, network: Cidr },
+    InvalidSplit { prefix_len: u8, new_prefix_len: u8, max: u8 },
+}
+
+impl fmt::Display for CidrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CidrError::MissingPrefix(input) => write!(f, "'{input}' has no prefix length, expected ADDRESS/PREFIX such as 10.0.0.0/8"),
+            CidrError::Address(error) => write!(f, "{error}"),
+            CidrError::InvalidPrefix(prefix) => write!(f, "'{prefix}' is not a valid prefix length"),
+            CidrError::PrefixTooLong { prefix_len, max } => {
+                write!(f, "prefix length {prefix_len} is longer than the {max} bits of the address")
+            }
+            CidrError::HostBitsSet { input, network } => {
+                write!(f, "'{input}' has host bits set, did you mean {network}?")
+            }
+            CidrError::InvalidSplit { prefix_len, new_prefix_len, max } => {
+                write!(f, "cannot split a /{prefix_len} into /{new_prefix_len} subnets, expected a prefix length between {prefix_len} and {max}")
+            }
+        }
+    }
+}
+
+impl Error for CidrError {}
+
+impl From<AddrParseError> for CidrError {
+    fn from(error: AddrParseError) -> CidrError {
+        CidrError::Address(error)
+    }
+}
+
+impl Cidr {
+    // 地址里的主机位会被清掉，所以 Cidr::new(192.168.1.20, 24) 得到 192.168.1.0/24
+    pub fn new(address: IpAddr, prefix_len: u8) -> Result<Cidr, CidrError> {
+        let max = address.kind().bits();
+        if prefix_len > max {
+            return Err(CidrError::PrefixTooLong { prefix_len, max });
+        }
+        let network = IpAddr::from_bits(address.kind(), address.to_bits() & mask(address.kind(), prefix_len));
+        Ok(Cidr { network, prefix_len })
+    }
+
+    pub fn kind(&self) -> IpAddrKind {
+        self.network.kind()
+    }
+
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    pub fn network(&self) -> IpAddr {
+        self.network
+    }
+
+    pub fn netmask(&self) -> IpAddr {
+        IpAddr::from_bits(self.kind(), mask(self.kind(), self.prefix_len))
+    }
+
+    // 网段里的最后一个地址；IPv6 没有广播，这里同样返回最后一个地址
+    pub fn broadcast(&self) -> IpAddr {
+        IpAddr::from_bits(self.kind(), self.last_bits())
+    }
+
+    pub fn contains(&self, address: &IpAddr) -> bool {
+        address.kind() == self.kind() && address.to_bits() & mask(self.kind(), self.prefix_len) == self.network.to_bits()
+    }
+
+    pub fn contains_cidr(&self, other: &Cidr) -> bool {
+        other.prefix_len >= self.prefix_len && self.contains(&other.network)
+    }
+
+    // IPv4 去掉网络地址和广播地址，/31 和 /32 例外（RFC 3021）；
+    // IPv6 的每个地址都可用，::/0 的数量超出 u128，返回 u128::MAX
+    pub fn host_count(&self) -> u128 {
+        let host_bits = self.kind().bits() - self.prefix_len;
+        match (self.kind(), host_bits) {
+            (IpAddrKind::V4, 0..=1) => 1 << host_bits,
+            (IpAddrKind::V4, _) => (1 << host_bits) - 2,
+            (IpAddrKind::V6, 128) => u128::MAX,
+            (IpAddrKind::V6, _) => 1 << host_bits,
+        }
+    }
+
+    // 按更长的前缀切成子网，结果很多时也是一个一个按需生成
+    pub fn split(&self, new_prefix_len: u8) -> Result<Subnets, CidrError> {
+        let max = self.kind().bits();
+        if new_prefix_len < self.prefix_len || new_prefix_len > max {
+            return Err(CidrError::InvalidSplit { prefix_len: self.prefix_len, new_prefix_len, max });
+        }
+        Ok(Subnets {
+            kind: self.kind(),
+            next: Some(self.network.to_bits()),
+            last: self.last_bits(),
+            prefix_len: new_prefix_len,
+        })
+    }
+
+    fn last_bits(&self) -> u128 {
+        self.network.to_bits() | (!mask(self.kind(), self.prefix_len) & full_mask(self.kind()))
+    }
+
+    // 两个前缀长度相同、首尾相接且能对齐到上一级的网段可以合并
+    fn merge_sibling(&self, other: &Cidr) -> Option<Cidr> {
+        if self.kind() != other.kind() || self.prefix_len != other.prefix_len || self.prefix_len == 0 {
+            return None;
+        }
+        let parent = Cidr::new(self.network, self.prefix_len - 1).ok()?;
+        if parent.network == self.network && self.last_bits().checked_add(1) == Some(other.network.to_bits()) {
+            Some(parent)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&format!("{}/{}", self.network, self.prefix_len))
+    }
+}
+
+// 地址必须是网络地址本身，主机位不为 0 时报错并给出正确的写法
+impl FromStr for Cidr {
+    type Err = CidrError;
+
+    fn from_str(s: &str) -> Result<Cidr, CidrError> {
+        let input = s.trim();
+        let (address, prefix) = input.split_once('/').ok_or_else(|| CidrError::MissingPrefix(input.to_string()))?;
+        let address: IpAddr = address.parse()?;
+        let prefix = prefix.trim();
+        let prefix_len = match prefix.parse() {
+            Ok(prefix_len) if prefix.bytes().all(|b| b.is_ascii_digit()) => prefix_len,
+            _ => return Err(CidrError::InvalidPrefix(prefix.to_string())),
+        };
+        let cidr = Cidr::new(address, prefix_len)?;
+        if cidr.network != address {
+            return Err(CidrError::HostBitsSet { input: input.to_string(), network: cidr });
+        }
+        Ok(cidr)
+    }
+}
+
+pub struct Subnets {
+    kind: IpAddrKind,
+    next: Option<u128>,
+    last: u128,
+    prefix_len: u8,
+}
+
+impl Iterator for Subnets {
+    type Item = Cidr;
+
+    fn next(&mut self) -> Option<Cidr> {
+        let bits = self.next?;
+        let step = 1u128.checked_shl(u32::from(self.kind.bits() - self.prefix_len));
+        self.next = step.and_then(|step| bits.checked_add(step)).filter(|&next| next <= self.last);
+        Some(Cidr { network: IpAddr::from_bits(self.kind, bits), prefix_len: self.prefix_len })
+    }
+}
+
+// 去掉被包含的网段，再把相邻的兄弟网段合并成更短的前缀，直到不能再合并为止
+pub fn aggregate(cidrs: &[Cidr]) -> Vec<Cidr> {
+    let mut sorted = cidrs.to_vec();
+    sorted.sort();
+
+    let mut merged: Vec<Cidr> = Vec::new();
+    for cidr in sorted {
+        if merged.last().is_some_and(|last| last.contains_cidr(&cidr)) {
+            continue;
+        }
+        merged.push(cidr);
+        while merged.len() >= 2 {
+            let parent = match merged[merged.len() - 2].merge_sibling(&merged[merged.len() - 1]) {
+                Some(parent) => parent,
+                None => break,
+            };
+            merged.truncate(merged.len() - 2);
+            merged.push(parent);
+        }
+    }
+    merged
+}
+
+fn full_mask(kind: IpAddrKind) -> u128 {
+    match kind {
+        IpAddrKind::V4 => u128::from(u32::MAX),
+        IpAddrKind::V6 => u128::MAX,
+    }
+}
+
+fn mask(kind: IpAddrKind, prefix_len: u8) -> u128 {
+    match prefix_len {
+        0 => 0,
+        prefix_len => (u128::MAX << (kind.bits() - prefix_len)) & full_mask(kind),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cidr(input: &str) -> Cidr {
+        input.parse().unwrap_or_else(|error| panic!("{input}: {error}"))
+    }
+
+    fn cidrs(inputs: &[&str]) -> Vec<Cidr> {
+        inputs.iter().map(|input| cidr(input)).collect()
+    }
+
+    fn strings(cidrs: impl IntoIterator<Item = Cidr>) -> Vec<String> {
+        cidrs.into_iter().map(|cidr| cidr.to_string()).collect()
+    }
+
+    #[test]
+    fn valid_cidrs() {
+        let cases = [
+            ("10.0.0.0/8", "10.0.0.0/8"),
+            (" 192.168.1.0 / 24 ", "192.168.1.0/24"),
+            ("0.0.0.0/0", "0.0.0.0/0"),
+            ("255.255.255.255/32", "255.255.255.255/32"),
+            ("::/0", "::/0"),
+            ("2001:DB8::/32", "2001:db8::/32"),
+            ("::1/128", "::1/128"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(cidr(input).to_string(), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn invalid_cidrs() {
+        let cases = [
+            ("10.0.0.0", CidrError::MissingPrefix(String::from("10.0.0.0"))),
+            ("10.0.0.1/8", CidrError::HostBitsSet { input: String::from("10.0.0.1/8"), network: cidr("10.0.0.0/8") }),
+            ("192.168.1.1/31", CidrError::HostBitsSet { input: String::from("192.168.1.1/31"), network: cidr("192.168.1.0/31") }),
+            ("2001:db8::1/32", CidrError::HostBitsSet { input: String::from("2001:db8::1/32"), network: cidr("2001:db8::/32") }),
+            ("10.0.0.0/33", CidrError::PrefixTooLong { prefix_len: 33, max: 32 }),
+            ("::/129", CidrError::PrefixTooLong { prefix_len: 129, max: 128 }),
+            ("10.0.0.0/", CidrError::InvalidPrefix(String::new())),
+            ("10.0.0.0/+8", CidrError::InvalidPrefix(String::from("+8"))),
+            ("10.0.0.0/abc", CidrError::InvalidPrefix(String::from("abc"))),
+            ("10.0.0.0/256", CidrError::InvalidPrefix(String::from("256"))),
+            ("/8", CidrError::Address(AddrParseError::Empty)),
+            (
+                "10.0.0/8",
+                CidrError::Address(AddrParseError::InvalidV4 { input: String::from("10.0.0"), reason: "expected 4 octets" }),
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<Cidr>(), Err(expected), "{input}");
+        }
+        assert_eq!(
+            "10.0.0.1/8".parse::<Cidr>().unwrap_err().to_string(),
+            "'10.0.0.1/8' has host bits set, did you mean 10.0.0.0/8?"
+        );
+    }
+
+    #[test]
+    fn new_clears_host_bits() {
+        let address = IpAddr::V4(192, 168, 1, 20);
+        assert_eq!(Cidr::new(address, 24), Ok(cidr("192.168.1.0/24")));
+        assert_eq!(Cidr::new(address, 0), Ok(cidr("0.0.0.0/0")));
+        assert_eq!(Cidr::new(address, 32).unwrap().network(), address);
+        assert_eq!(Cidr::new(address, 33), Err(CidrError::PrefixTooLong { prefix_len: 33, max: 32 }));
+    }
+
+    #[test]
+    fn masks_and_membership() {
+        let cases = [
+            ("192.168.1.0/24", "255.255.255.0", "192.168.1.255"),
+            ("10.0.0.0/8", "255.0.0.0", "10.255.255.255"),
+            ("0.0.0.0/0", "0.0.0.0", "255.255.255.255"),
+            ("172.16.5.4/32", "255.255.255.255", "172.16.5.4"),
+            ("2001:db8::/32", "ffff:ffff::", "2001:db8:ffff:ffff:ffff:ffff:ffff:ffff"),
+            ("::/0", "::", "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff"),
+        ];
+        for (input, netmask, broadcast) in cases {
+            let cidr = cidr(input);
+            assert_eq!(cidr.netmask().to_string(), netmask, "{input}");
+            assert_eq!(cidr.broadcast().to_string(), broadcast, "{input}");
+        }
+
+        let network = cidr("192.168.1.0/24");
+        assert!(network.contains(&"192.168.1.77".parse().unwrap()));
+        assert!(!network.contains(&"192.168.2.1".parse().unwrap()));
+        assert!(!cidr("0.0.0.0/0").contains(&"::".parse().unwrap()));
+        assert!(!cidr("::/0").contains(&"0.0.0.0".parse().unwrap()));
+        assert!(network.contains_cidr(&cidr("192.168.1.128/25")));
+        assert!(network.contains_cidr(&network));
+        assert!(!cidr("192.168.1.128/25").contains_cidr(&network));
+    }
+
+    #[test]
+    fn host_counts() {
+        let cases = [
+            ("10.0.0.0/8", 16_777_214),
+            ("192.168.1.0/24", 254),
+            ("192.168.1.0/30", 2),
+            ("192.168.1.0/31", 2),
+            ("192.168.1.1/32", 1),
+            ("0.0.0.0/0", (1 << 32) - 2),
+            ("2001:db8::/64", 1 << 64),
+            ("::/1", 1 << 127),
+            ("::1/128", 1),
+            ("::/0", u128::MAX),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(cidr(input).host_count(), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn split_into_subnets() {
+        let cases: [(&str, u8, &[&str]); 8] = [
+            ("10.0.0.0/24", 24, &["10.0.0.0/24"]),
+            ("10.0.0.0/24", 26, &["10.0.0.0/26", "10.0.0.64/26", "10.0.0.128/26", "10.0.0.192/26"]),
+            ("192.168.1.0/30", 32, &["192.168.1.0/32", "192.168.1.1/32", "192.168.1.2/32", "192.168.1.3/32"]),
+            ("255.255.255.252/30", 32, &["255.255.255.252/32", "255.255.255.253/32", "255.255.255.254/32", "255.255.255.255/32"]),
+            ("0.0.0.0/0", 1, &["0.0.0.0/1", "128.0.0.0/1"]),
+            ("::/0", 0, &["::/0"]),
+            ("::/126", 128, &["::/128", "::1/128", "::2/128", "::3/128"]),
+            (
+                "ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe/127",
+                128,
+                &["ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe/128", "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128"],
+            ),
+        ];
+        for (input, new_prefix_len, expected) in cases {
+            let subnets = cidr(input).split(new_prefix_len).unwrap();
+            assert_eq!(strings(subnets), *expected, "{input} -> /{new_prefix_len}");
+        }
+
+        // 结果按需生成，切出 2^128 个 /128 也不会卡住
+        let first = cidr("::/0").split(128).unwrap().take(3);
+        assert_eq!(strings(first), ["::/128", "::1/128", "::2/128"]);
+    }
+
+    #[test]
+    fn invalid_splits() {
+        let cases = [
+            ("10.0.0.0/24", 23, CidrError::InvalidSplit { prefix_len: 24, new_prefix_len: 23, max: 32 }),
+            ("10.0.0.0/24", 33, CidrError::InvalidSplit { prefix_len: 24, new_prefix_len: 33, max: 32 }),
+            ("::/64", 129, CidrError::InvalidSplit { prefix_len: 64, new_prefix_len: 129, max: 128 }),
+        ];
+        for (input, new_prefix_len, expected) in cases {
+            assert_eq!(cidr(input).split(new_prefix_len).err(), Some(expected), "{input} -> /{new_prefix_len}");
+        }
+    }
+
+    #[test]
+    fn aggregation() {
+        let cases: [(&[&str], &[&str]); 14] = [
+            (&[], &[]),
+            // 兄弟网段合并，并且一路向上合并
+            (&["10.0.0.0/25", "10.0.0.128/25"], &["10.0.0.0/24"]),
+            (&["10.0.2.0/23", "10.0.1.0/24", "10.0.0.0/24"], &["10.0.0.0/22"]),
+            (&["255.255.255.254/32", "255.255.255.255/32"], &["255.255.255.254/31"]),
+            (&["0.0.0.0/1", "128.0.0.0/1"], &["0.0.0.0/0"]),
+            // 被包含的网段去掉
+            (&["10.1.0.0/16", "10.0.0.0/8", "10.255.255.255/32"], &["10.0.0.0/8"]),
+            (&["10.0.0.0/24", "10.0.0.0/16"], &["10.0.0.0/16"]),
+            (&["10.0.0.0/24", "10.0.0.0/24"], &["10.0.0.0/24"]),
+            (&["10.0.0.128/25", "10.0.0.64/26", "10.0.0.0/25"], &["10.0.0.0/24"]),
+            // 相邻但对不齐、或者不相邻的网段保持原样
+            (&["10.0.1.0/24", "10.0.2.0/24"], &["10.0.1.0/24", "10.0.2.0/24"]),
+            (&["10.0.2.0/24", "10.0.0.0/24"], &["10.0.0.0/24", "10.0.2.0/24"]),
+            (&["10.0.0.0/24", "10.0.1.0/25"], &["10.0.0.0/24", "10.0.1.0/25"]),
+            // IPv4 和 IPv6 各自合并，互不影响
+            (&["8000::/1", "128.0.0.0/1", "::/1", "0.0.0.0/1"], &["0.0.0.0/0", "::/0"]),
+            (&["0.0.0.0/32", "::1/128", "::/128", "0.0.0.1/32"], &["0.0.0.0/31", "::/127"]),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(strings(aggregate(&cidrs(input))), *expected, "{input:?}");
+        }
+    }
+}
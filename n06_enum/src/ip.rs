@@ -0,0 +1,375 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IpAddrKind {
+    V4,
+    V6,
+}
+
+// V4 沿用教程里的四个 u8，V6 换成八个 16 位的分组，不再是随便一个 String
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum IpAddr {
+    V4(u8, u8, u8, u8),
+    V6([u16; 8]),
+}
+
+impl IpAddrKind {
+    pub fn bits(&self) -> u8 {
+        match self {
+            IpAddrKind::V4 => 32,
+            IpAddrKind::V6 => 128,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddrParseError {
+    Empty,
+    InvalidV4 { input: String, reason: &'static str },
+    InvalidV6 { input: String, reason: &'static str },
+}
+
+impl fmt::Display for AddrParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AddrParseError::Empty => write!(f, "empty IP address"),
+            AddrParseError::InvalidV4 { input, reason } => write!(f, "'{input}' is not a valid IPv4 address: {reason}"),
+            AddrParseError::InvalidV6 { input, reason } => write!(f, "'{input}' is not a valid IPv6 address: {reason}"),
+        }
+    }
+}
+
+impl Error for AddrParseError {}
+
+impl IpAddr {
+    pub fn kind(&self) -> IpAddrKind {
+        match self {
+            IpAddr::V4(..) => IpAddrKind::V4,
+            IpAddr::V6(_) => IpAddrKind::V6,
+        }
+    }
+
+    // 地址当成一个整数，IPv4 只用低 32 位
+    pub fn to_bits(&self) -> u128 {
+        match *self {
+            IpAddr::V4(a, b, c, d) => u128::from(u32::from_be_bytes([a, b, c, d])),
+            IpAddr::V6(groups) => groups.iter().fold(0, |bits, &group| (bits << 16) | u128::from(group)),
+        }
+    }
+
+    pub fn from_bits(kind: IpAddrKind, bits: u128) -> IpAddr {
+        match kind {
+            IpAddrKind::V4 => {
+                let [a, b, c, d] = (bits as u32).to_be_bytes();
+                IpAddr::V4(a, b, c, d)
+            }
+            IpAddrKind::V6 => {
+                let mut groups = [0; 8];
+                for (index, group) in groups.iter_mut().enumerate() {
+                    *group = (bits >> (16 * (7 - index))) as u16;
+                }
+                IpAddr::V6(groups)
+            }
+        }
+    }
+
+    // 127.0.0.0/8 和 ::1
+    pub fn is_loopback(&self) -> bool {
+        match self {
+            IpAddr::V4(a, ..) => *a == 127,
+            IpAddr::V6(groups) => *groups == [0, 0, 0, 0, 0, 0, 0, 1],
+        }
+    }
+
+    // RFC 1918 的三个私有网段，IPv6 对应的是唯一本地地址 fc00::/7
+    pub fn is_private(&self) -> bool {
+        match *self {
+            IpAddr::V4(10, ..) => true,
+            IpAddr::V4(172, b, ..) => (16..=31).contains(&b),
+            IpAddr::V4(192, 168, ..) => true,
+            IpAddr::V4(..) => false,
+            IpAddr::V6(groups) => groups[0] & 0xfe00 == 0xfc00,
+        }
+    }
+
+    // 224.0.0.0/4 和 ff00::/8
+    pub fn is_multicast(&self) -> bool {
+        match self {
+            IpAddr::V4(a, ..) => (224..=239).contains(a),
+            IpAddr::V6(groups) => groups[0] & 0xff00 == 0xff00,
+        }
+    }
+}
+
+// IPv6 按 RFC 5952 输出：小写、去掉前导零、最长的一段连续零分组写成 ::
+impl fmt::Display for IpAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IpAddr::V4(a, b, c, d) => f.pad(&format!("{a}.{b}.{c}.{d}")),
+            IpAddr::V6(groups) => f.pad(&format_v6(groups)),
+        }
+    }
+}
+
+fn format_v6(groups: &[u16; 8]) -> String {
+    // ::ffff:0:0/96 是映射到 IPv6 的 IPv4 地址，最后 32 位写成点分十进制
+    if groups[..5] == [0; 5] && groups[5] == 0xffff {
+        let [a, b] = groups[6].to_be_bytes();
+        let [c, d] = groups[7].to_be_bytes();
+        return format!("::ffff:{a}.{b}.{c}.{d}");
+    }
+
+    // 只有一个零分组时不压缩，长度相同时压缩最靠前的一段
+    let mut longest = None;
+    let mut start = 0;
+    while start < groups.len() {
+        let length = groups[start..].iter().take_while(|&&group| group == 0).count();
+        if length >= 2 && longest.is_none_or(|(_, best)| length > best) {
+            longest = Some((start, length));
+        }
+        start += length.max(1);
+    }
+
+    let join = |groups: &[u16]| groups.iter().map(|group| format!("{group:x}")).collect::<Vec<_>>().join(":");
+    match longest {
+        Some((start, length)) => format!("{}::{}", join(&groups[..start]), join(&groups[start + length..])),
+        None => join(groups),
+    }
+}
+
+impl FromStr for IpAddr {
+    type Err = AddrParseError;
+
+    fn from_str(s: &str) -> Result<IpAddr, AddrParseError> {
+        let input = s.trim();
+        if input.is_empty() {
+            return Err(AddrParseError::Empty);
+        }
+        if input.contains(':') {
+            let groups = parse_v6(input)
+                .map_err(|reason| AddrParseError::InvalidV6 { input: input.to_string(), reason })?;
+            return Ok(IpAddr::V6(groups));
+        }
+        let [a, b, c, d] = parse_v4(input)
+            .map_err(|reason| AddrParseError::InvalidV4 { input: input.to_string(), reason })?;
+        Ok(IpAddr::V4(a, b, c, d))
+    }
+}
+
+// 不接受 01 这样的前导零，免得和八进制写法混淆
+fn parse_v4(input: &str) -> Result<[u8; 4], &'static str> {
+    let mut octets = [0; 4];
+    let mut parts = input.split('.');
+    for octet in octets.iter_mut() {
+        let part = parts.next().ok_or("expected 4 octets")?;
+        if part.is_empty() {
+            return Err("empty octet");
+        }
+        if !part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err("octets must be decimal numbers");
+        }
+        if part.len() > 1 && part.starts_with('0') {
+            return Err("octets must not have leading zeros");
+        }
+        *octet = part.parse().map_err(|_| "octets must be between 0 and 255")?;
+    }
+    if parts.next().is_some() {
+        return Err("expected 4 octets");
+    }
+    Ok(octets)
+}
+
+fn parse_v6(input: &str) -> Result<[u16; 8], &'static str> {
+    let (head, tail) = match input.split_once("::") {
+        Some((_, tail)) if tail.contains("::") => return Err("'::' can only appear once"),
+        Some((head, tail)) => (parse_groups(head, false)?, Some(parse_groups(tail, true)?)),
+        None => (parse_groups(input, true)?, None),
+    };
+
+    let mut groups = [0; 8];
+    match tail {
+        Some(tail) => {
+            if head.len() + tail.len() > 7 {
+                return Err("too many groups to use '::'");
+            }
+            groups[..head.len()].copy_from_slice(&head);
+            groups[8 - tail.len()..].copy_from_slice(&tail);
+        }
+        None => {
+            if head.len() != 8 {
+                return Err("expected 8 groups");
+            }
+            groups.copy_from_slice(&head);
+        }
+    }
+    Ok(groups)
+}
+
+// 最后一个分组可以是点分十进制的 IPv4 地址，占两个分组
+fn parse_groups(text: &str, ipv4_allowed: bool) -> Result<Vec<u16>, &'static str> {
+    let mut groups = Vec::new();
+    if text.is_empty() {
+        return Ok(groups);
+    }
+
+    let mut parts = text.split(':').peekable();
+    while let Some(part) = parts.next() {
+        if part.contains('.') {
+            if !ipv4_allowed || parts.peek().is_some() {
+                return Err("an embedded IPv4 address must come last");
+            }
+            let [a, b, c, d] = parse_v4(part).map_err(|_| "invalid embedded IPv4 address")?;
+            groups.push(u16::from_be_bytes([a, b]));
+            groups.push(u16::from_be_bytes([c, d]));
+        } else if part.is_empty() {
+            return Err("empty group");
+        } else if !part.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err("groups must be hex numbers");
+        } else if part.len() > 4 {
+            return Err("groups have at most 4 hex digits");
+        } else {
+            groups.push(u16::from_str_radix(part, 16).expect("checked hex digits"));
+        }
+        if groups.len() > 8 {
+            return Err("too many groups");
+        }
+    }
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v6(groups: [u16; 8]) -> IpAddr {
+        IpAddr::V6(groups)
+    }
+
+    #[test]
+    fn valid_addresses() {
+        let cases = [
+            ("127.0.0.1", IpAddr::V4(127, 0, 0, 1)),
+            ("0.0.0.0", IpAddr::V4(0, 0, 0, 0)),
+            ("255.255.255.255", IpAddr::V4(255, 255, 255, 255)),
+            (" 10.0.0.1 ", IpAddr::V4(10, 0, 0, 1)),
+            ("::", v6([0; 8])),
+            ("::1", v6([0, 0, 0, 0, 0, 0, 0, 1])),
+            ("1::", v6([1, 0, 0, 0, 0, 0, 0, 0])),
+            ("1:2:3:4:5:6:7::", v6([1, 2, 3, 4, 5, 6, 7, 0])),
+            ("::2:3:4:5:6:7:8", v6([0, 2, 3, 4, 5, 6, 7, 8])),
+            ("2001:0DB8:0000:0000:0000:ff00:0042:8329", v6([0x2001, 0xdb8, 0, 0, 0, 0xff00, 0x42, 0x8329])),
+            ("::ffff:1.2.3.4", v6([0, 0, 0, 0, 0, 0xffff, 0x0102, 0x0304])),
+            ("64:ff9b::192.0.2.33", v6([0x64, 0xff9b, 0, 0, 0, 0, 0xc000, 0x0221])),
+            ("1:2:3:4:5:6:1.2.3.4", v6([1, 2, 3, 4, 5, 6, 0x0102, 0x0304])),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse(), Ok(expected), "{input}");
+        }
+    }
+
+    #[test]
+    fn invalid_addresses() {
+        let v4 = |input: &str, reason| Err(AddrParseError::InvalidV4 { input: input.trim().to_string(), reason });
+        let v6 = |input: &str, reason| Err(AddrParseError::InvalidV6 { input: input.trim().to_string(), reason });
+        let cases = [
+            ("", Err(AddrParseError::Empty)),
+            ("   ", Err(AddrParseError::Empty)),
+            ("256.1.1.1", v4("256.1.1.1", "octets must be between 0 and 255")),
+            ("1.2.3", v4("1.2.3", "expected 4 octets")),
+            ("1.2.3.4.5", v4("1.2.3.4.5", "expected 4 octets")),
+            ("1..3.4", v4("1..3.4", "empty octet")),
+            ("01.2.3.4", v4("01.2.3.4", "octets must not have leading zeros")),
+            ("1.2.3.+4", v4("1.2.3.+4", "octets must be decimal numbers")),
+            ("1.2.3.0x4", v4("1.2.3.0x4", "octets must be decimal numbers")),
+            ("1::2::3", v6("1::2::3", "'::' can only appear once")),
+            (":::", v6(":::", "empty group")),
+            (":1::", v6(":1::", "empty group")),
+            ("1:", v6("1:", "empty group")),
+            ("12345::", v6("12345::", "groups have at most 4 hex digits")),
+            ("+1::", v6("+1::", "groups must be hex numbers")),
+            ("g::", v6("g::", "groups must be hex numbers")),
+            ("1.2.3.4::", v6("1.2.3.4::", "an embedded IPv4 address must come last")),
+            ("1.2.3.4:1::", v6("1.2.3.4:1::", "an embedded IPv4 address must come last")),
+            ("::1.2.3.256", v6("::1.2.3.256", "invalid embedded IPv4 address")),
+            ("1:2:3:4:5:6:7", v6("1:2:3:4:5:6:7", "expected 8 groups")),
+            ("1:2:3:4:5:6:7:8:9", v6("1:2:3:4:5:6:7:8:9", "too many groups")),
+            ("1:2:3:4:5:6:7:8::", v6("1:2:3:4:5:6:7:8::", "too many groups to use '::'")),
+            ("1:2:3:4:5:6:7::1.2.3.4", v6("1:2:3:4:5:6:7::1.2.3.4", "too many groups to use '::'")),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<IpAddr>(), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn display_is_canonical() {
+        let cases = [
+            ("127.0.0.1", "127.0.0.1"),
+            ("2001:0DB8:0000:0000:0000:FF00:0042:8329", "2001:db8::ff00:42:8329"),
+            // 最长的一段零被压缩
+            ("1:0:0:1:0:0:0:1", "1:0:0:1::1"),
+            // 一样长时压缩最前面的一段
+            ("1:0:0:1:0:0:1:1", "1::1:0:0:1:1"),
+            // 只有一个零分组时不压缩
+            ("1:0:1:1:1:1:1:1", "1:0:1:1:1:1:1:1"),
+            ("1:2:3:4:5:6:7::", "1:2:3:4:5:6:7:0"),
+            ("0:0:0:0:0:0:0:0", "::"),
+            ("0:0:0:0:0:0:0:1", "::1"),
+            ("1:0:0:0:0:0:0:0", "1::"),
+            ("::ffff:1.2.3.4", "::ffff:1.2.3.4"),
+            ("::ffff:0102:0304", "::ffff:1.2.3.4"),
+            ("::1.2.3.4", "::102:304"),
+        ];
+        for (input, canonical) in cases {
+            let address: IpAddr = input.parse().unwrap();
+            assert_eq!(address.to_string(), canonical, "{input}");
+            assert_eq!(canonical.parse(), Ok(address), "{canonical}");
+        }
+    }
+
+    #[test]
+    fn classification() {
+        // (地址, loopback, private, multicast)
+        let cases = [
+            ("127.0.0.1", true, false, false),
+            ("127.255.255.254", true, false, false),
+            ("10.1.2.3", false, true, false),
+            ("172.15.255.255", false, false, false),
+            ("172.16.0.0", false, true, false),
+            ("172.31.255.255", false, true, false),
+            ("172.32.0.0", false, false, false),
+            ("192.168.1.1", false, true, false),
+            ("192.169.0.1", false, false, false),
+            ("223.255.255.255", false, false, false),
+            ("224.0.0.1", false, false, true),
+            ("239.255.255.255", false, false, true),
+            ("240.0.0.1", false, false, false),
+            ("8.8.8.8", false, false, false),
+            ("::1", true, false, false),
+            ("::", false, false, false),
+            ("fc00::1", false, true, false),
+            ("fdff:ffff::1", false, true, false),
+            ("fe00::1", false, false, false),
+            ("ff02::1", false, false, true),
+            ("2001:db8::1", false, false, false),
+            ("::ffff:127.0.0.1", false, false, false),
+        ];
+        for (input, loopback, private, multicast) in cases {
+            let address: IpAddr = input.parse().unwrap();
+            assert_eq!(address.is_loopback(), loopback, "{input} loopback");
+            assert_eq!(address.is_private(), private, "{input} private");
+            assert_eq!(address.is_multicast(), multicast, "{input} multicast");
+        }
+    }
+
+    #[test]
+    fn bits_round_trip() {
+        for input in ["0.0.0.0", "192.168.1.20", "255.255.255.255", "::", "2001:db8::ff00:42:8329", "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff"] {
+            let address: IpAddr = input.parse().unwrap();
+            assert_eq!(IpAddr::from_bits(address.kind(), address.to_bits()), address, "{input}");
+        }
+        assert_eq!("1.2.3.4".parse::<IpAddr>().unwrap().to_bits(), 0x0102_0304);
+    }
+}
@@ -1,6 +1,15 @@
 
 #![allow(unused)]
 
+use std::env;
+use std::io::{self, BufRead};
+use std::path::Path;
+use std::process;
+
+use n06_enum::cidr::{self, Cidr};
+use n06_enum::ip::IpAddr;
+use n06_enum::route::{self, RoutingTable};
+
 // base define
 // fn main() {
 //     #[derive(Debug)]
@@ -297,7 +306,15 @@ enum Coin {
 //     }
 // }
 
+// 用法：n06_enum ROUTES_FILE [ADDRESS...]，没有给地址时从标准输入一行读一个；
+// 不带参数时运行下面的示例
 fn main() {
+    let mut args = env::args().skip(1);
+    if let Some(path) = args.next() {
+        look_up(Path::new(&path), args.collect());
+        return;
+    }
+
     let coin = Coin::Penny;
     let mut count = 0;
     if let Coin::Quarter(state) = coin {
@@ -305,4 +322,96 @@ fn main() {
     } else {
         count += 1;
     }
+
+    // 解析并分类地址，更多容易出错的输入见 ip.rs 里的测试
+    for input in ["127.0.0.1", "192.168.1.20", "2001:0DB8:0000:0000:0000:ff00:0042:8329", "ff02::fb", "1::2::3"] {
+        match input.parse::<IpAddr>() {
+            Ok(ip) => println!(
+                "{input:<42} -> {ip:<24} loopback: {:<5} private: {:<5} multicast: {}",
+                ip.is_loopback(),
+                ip.is_private(),
+                ip.is_multicast()
+            ),
+            Err(error) => println!("{input:<42} -> {error}"),
+        }
+    }
+
+    // 子网计算
+    for input in ["10.0.0.0/8", "192.168.1.0/24", "192.168.1.4/31", "2001:db8::/32", "::/0"] {
+        let network: Cidr = input.parse().expect("valid CIDR");
+        println!(
+            "{network:<16} network: {:<12} netmask: {:<16} last: {:<40} hosts: {}",
+            network.network(),
+            network.netmask(),
+            network.broadcast(),
+            network.host_count()
+        );
+    }
+    for input in ["192.168.1.20/24", "10.0.0.0/33", "10.0.0.0", "10.0.0.0/x", "fe80::/129"] {
+        if let Err(error) = input.parse::<Cidr>() {
+            println!("error: {error}");
+        }
+    }
+
+    let office: Cidr = "192.168.0.0/22".parse().expect("valid CIDR");
+    let printer: IpAddr = "192.168.3.40".parse().expect("valid address");
+    println!("{office} contains {printer}: {}", office.contains(&printer));
+    match office.split(24) {
+        Ok(subnets) => println!("{office} as /24s: {}", subnets.map(|subnet| subnet.to_string()).collect::<Vec<_>>().join(", ")),
+        Err(error) => println!("error: {error}"),
+    }
+    if let Err(error) = office.split(16) {
+        println!("error: {error}");
+    }
+
+    let routes: Vec<Cidr> = ["10.0.0.0/24", "10.0.1.0/24", "10.0.2.0/23", "10.0.1.128/25", "10.1.0.0/24", "2001:db8::/33", "2001:db8:8000::/33"]
+        .iter()
+        .map(|input| input.parse().expect("valid CIDR"))
+        .collect();
+    let aggregated = cidr::aggregate(&routes);
+    println!("aggregated: {}", aggregated.iter().map(|cidr| cidr.to_string()).collect::<Vec<_>>().join(", "));
+}
+
+fn look_up(path: &Path, addresses: Vec<String>) {
+    let file = match route::load(path) {
+        Ok(file) => file,
+        Err(error) => {
+            eprintln!("Could not read {}: {error}", path.display());
+            process::exit(1);
+        }
+    };
+    for (line, error) in &file.errors {
+        eprintln!("{}:{line}: {error}", path.display());
+    }
+
+    if addresses.is_empty() {
+        for line in io::stdin().lock().lines() {
+            match line {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => print_route(&file.table, &line),
+                Err(error) => {
+                    eprintln!("Failed to read stdin: {error}");
+                    process::exit(1);
+                }
+            }
+        }
+    } else {
+        for address in &addresses {
+            print_route(&file.table, address);
+        }
+    }
+}
+
+fn print_route(table: &RoutingTable, input: &str) {
+    let address: IpAddr = match input.parse() {
+        Ok(address) => address,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return;
+        }
+    };
+    match table.route(&address) {
+        Some(route) => println!("{address} -> {} (via {})", route.next_hop, route.prefix),
+        None => println!("{address} -> no route"),
+    }
 }
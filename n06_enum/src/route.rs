@@ -0,0 +1,306 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::cidr::{Cidr, CidrError};
+use crate::ip::{IpAddr, IpAddrKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub prefix: Cidr,
+    pub next_hop: String,
+}
+
+// 二叉前缀树：从地址最高位开始，每一位决定往左还是往右走，
+// 路过的节点上挂着的路由就是能匹配的前缀，最深的那个就是最长前缀匹配
+#[derive(Debug, Default)]
+struct Node {
+    children: [Option<Box<Node>>; 2],
+    route: Option<Route>,
+}
+
+#[derive(Debug, Default)]
+pub struct RoutingTable {
+    v4: Node,
+    v6: Node,
+    len: usize,
+}
+
+impl RoutingTable {
+    pub fn new() -> RoutingTable {
+        RoutingTable::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // 同一个前缀再次插入时替换下一跳，返回原来的路由
+    pub fn insert(&mut self, prefix: Cidr, next_hop: &str) -> Option<Route> {
+        let bits = prefix.network().to_bits();
+        let mut node = self.root_mut(prefix.kind());
+        for depth in 0..prefix.prefix_len() {
+            node = node.children[bit(prefix.kind(), bits, depth)].get_or_insert_with(Box::default);
+        }
+
+        let previous = node.route.replace(Route { prefix, next_hop: next_hop.to_string() });
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    // 只找前缀完全相同的路由
+    pub fn get(&self, prefix: &Cidr) -> Option<&Route> {
+        let bits = prefix.network().to_bits();
+        let mut node = self.root(prefix.kind());
+        for depth in 0..prefix.prefix_len() {
+            node = node.children[bit(prefix.kind(), bits, depth)].as_deref()?;
+        }
+        node.route.as_ref()
+    }
+
+    pub fn route(&self, address: &IpAddr) -> Option<&Route> {
+        let kind = address.kind();
+        let bits = address.to_bits();
+        let mut node = self.root(kind);
+        let mut best = node.route.as_ref();
+        for depth in 0..kind.bits() {
+            node = match &node.children[bit(kind, bits, depth)] {
+                Some(child) => child,
+                None => break,
+            };
+            best = node.route.as_ref().or(best);
+        }
+        best
+    }
+
+    fn root(&self, kind: IpAddrKind) -> &Node {
+        match kind {
+            IpAddrKind::V4 => &self.v4,
+            IpAddrKind::V6 => &self.v6,
+        }
+    }
+
+    fn root_mut(&mut self, kind: IpAddrKind) -> &mut Node {
+        match kind {
+            IpAddrKind::V4 => &mut self.v4,
+            IpAddrKind::V6 => &mut self.v6,
+        }
+    }
+}
+
+// 从最高位往下数的第 depth 位
+fn bit(kind: IpAddrKind, bits: u128, depth: u8) -> usize {
+    ((bits >> (kind.bits() - 1 - depth)) & 1) as usize
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteLineError {
+    MissingNextHop,
+    Prefix(CidrError),
+    Duplicate(Cidr),
+}
+
+impl fmt::Display for RouteLineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RouteLineError::MissingNextHop => write!(f, "expected PREFIX NEXT_HOP such as 10.0.0.0/8 core-1"),
+            RouteLineError::Prefix(error) => write!(f, "{error}"),
+            RouteLineError::Duplicate(prefix) => write!(f, "{prefix} is already in the table, ignoring this line"),
+        }
+    }
+}
+
+impl Error for RouteLineError {}
+
+// 读取结果：路由表，以及出错的行号（从 1 开始）和原因
+#[derive(Debug, Default)]
+pub struct RouteFile {
+    pub table: RoutingTable,
+    pub errors: Vec<(usize, RouteLineError)>,
+}
+
+// 每行一条路由：前缀、空白、下一跳标签；# 之后是注释，空行会被跳过
+pub fn parse(contents: &str) -> RouteFile {
+    let mut file = RouteFile::default();
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Err(error) = parse_line(&mut file.table, line) {
+            file.errors.push((number + 1, error));
+        }
+    }
+    file
+}
+
+fn parse_line(table: &mut RoutingTable, line: &str) -> Result<(), RouteLineError> {
+    let (prefix, next_hop) = line.split_once(char::is_whitespace).ok_or(RouteLineError::MissingNextHop)?;
+    let prefix: Cidr = prefix.parse().map_err(RouteLineError::Prefix)?;
+    let next_hop = next_hop.trim();
+    if next_hop.is_empty() {
+        return Err(RouteLineError::MissingNextHop);
+    }
+    // 文件里先出现的路由有效，重复的行报错但不覆盖
+    if table.get(&prefix).is_some() {
+        return Err(RouteLineError::Duplicate(prefix));
+    }
+    table.insert(prefix, next_hop);
+    Ok(())
+}
+
+pub fn load(path: &Path) -> io::Result<RouteFile> {
+    Ok(parse(&fs::read_to_string(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cidr(input: &str) -> Cidr {
+        input.parse().unwrap()
+    }
+
+    fn next_hop<'a>(table: &'a RoutingTable, address: &str) -> Option<&'a str> {
+        table.route(&address.parse().unwrap()).map(|route| route.next_hop.as_str())
+    }
+
+    fn table(routes: &[(&str, &str)]) -> RoutingTable {
+        let mut table = RoutingTable::new();
+        for &(prefix, next_hop) in routes {
+            table.insert(cidr(prefix), next_hop);
+        }
+        table
+    }
+
+    #[test]
+    fn longest_prefix_wins_over_the_default_route() {
+        let table = table(&[
+            ("0.0.0.0/0", "default"),
+            ("10.0.0.0/8", "core"),
+            ("10.1.0.0/16", "edge"),
+            ("10.1.2.3/32", "host"),
+        ]);
+        let cases = [
+            ("8.8.8.8", Some("default")),
+            ("10.200.0.1", Some("core")),
+            ("10.1.0.1", Some("edge")),
+            ("10.1.2.2", Some("edge")),
+            ("10.1.2.3", Some("host")),
+            ("10.1.2.4", Some("edge")),
+            ("11.0.0.0", Some("default")),
+            ("255.255.255.255", Some("default")),
+        ];
+        for (address, expected) in cases {
+            assert_eq!(next_hop(&table, address), expected, "{address}");
+        }
+    }
+
+    #[test]
+    fn without_a_default_route_unmatched_addresses_have_no_route() {
+        let table = table(&[("10.0.0.0/8", "core"), ("2001:db8::/32", "core-v6")]);
+        assert_eq!(next_hop(&table, "11.0.0.1"), None);
+        assert_eq!(next_hop(&table, "2001:db9::1"), None);
+        assert_eq!(next_hop(&RoutingTable::new(), "10.0.0.1"), None);
+    }
+
+    #[test]
+    fn ipv4_and_ipv6_tables_are_separate() {
+        let v6_only = table(&[("::/0", "default-v6"), ("::/96", "compat")]);
+        assert_eq!(next_hop(&v6_only, "0.0.0.1"), None);
+        assert_eq!(next_hop(&v6_only, "::1"), Some("compat"));
+
+        let v4_only = table(&[("0.0.0.0/0", "default"), ("10.0.0.0/8", "core")]);
+        assert_eq!(next_hop(&v4_only, "::"), None);
+        assert_eq!(next_hop(&v4_only, "::ffff:10.0.0.1"), None);
+
+        // 数值相同的 0.0.0.0/0 和 ::/0 是两条不同的路由
+        let both = table(&[("0.0.0.0/0", "default"), ("::/0", "default-v6")]);
+        assert_eq!(both.len(), 2);
+        assert_eq!(next_hop(&both, "1.2.3.4"), Some("default"));
+        assert_eq!(next_hop(&both, "1:2:3::4"), Some("default-v6"));
+    }
+
+    #[test]
+    fn insert_returns_the_replaced_route() {
+        let mut table = RoutingTable::new();
+        assert!(table.is_empty());
+        assert_eq!(table.insert(cidr("10.0.0.0/8"), "core-1"), None);
+        assert_eq!(
+            table.insert(cidr("10.0.0.0/8"), "core-2"),
+            Some(Route { prefix: cidr("10.0.0.0/8"), next_hop: String::from("core-1") })
+        );
+        assert_eq!(table.len(), 1);
+        assert_eq!(next_hop(&table, "10.9.9.9"), Some("core-2"));
+    }
+
+    #[test]
+    fn get_only_finds_exact_prefixes() {
+        let table = table(&[("10.0.0.0/8", "core"), ("10.1.0.0/16", "edge")]);
+        assert_eq!(table.get(&cidr("10.1.0.0/16")).map(|route| route.next_hop.as_str()), Some("edge"));
+        assert_eq!(table.get(&cidr("10.0.0.0/16")), None);
+        assert_eq!(table.get(&cidr("10.1.0.0/24")), None);
+        assert_eq!(table.get(&cidr("0.0.0.0/0")), None);
+    }
+
+    #[test]
+    fn parse_skips_comments_and_blank_lines() {
+        let contents = "# 前缀 下一跳\n\n  10.0.0.0/8\tcore  # 核心\n   # 只有注释\n::/0 isp-v6\n";
+        let file = parse(contents);
+        assert!(file.errors.is_empty(), "{:?}", file.errors);
+        assert_eq!(file.table.len(), 2);
+        assert_eq!(next_hop(&file.table, "10.0.0.1"), Some("core"));
+        assert_eq!(next_hop(&file.table, "2001:db8::1"), Some("isp-v6"));
+    }
+
+    #[test]
+    fn parse_reports_bad_lines_and_keeps_the_first_duplicate() {
+        let contents = "\
+10.0.0.0/8 core-1
+10.0.0.0/8
+10.0.0.0/8 # core-3
+10.0.0.0/8 core-2
+10.0.0.1/8 core-4
+10.0.0.0 core-5
+192.168.0.0/16 office
+";
+        let file = parse(contents);
+        assert_eq!(
+            file.errors,
+            vec![
+                (2, RouteLineError::MissingNextHop),
+                (3, RouteLineError::MissingNextHop),
+                (4, RouteLineError::Duplicate(cidr("10.0.0.0/8"))),
+                (
+                    5,
+                    RouteLineError::Prefix(CidrError::HostBitsSet {
+                        input: String::from("10.0.0.1/8"),
+                        network: cidr("10.0.0.0/8"),
+                    })
+                ),
+                (6, RouteLineError::Prefix(CidrError::MissingPrefix(String::from("10.0.0.0")))),
+            ]
+        );
+        assert_eq!(file.table.len(), 2);
+        assert_eq!(next_hop(&file.table, "10.0.0.1"), Some("core-1"));
+        assert_eq!(next_hop(&file.table, "192.168.3.4"), Some("office"));
+    }
+
+    #[test]
+    fn sample_file_loads_without_errors() {
+        let file = load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("routes.txt")).unwrap();
+        assert!(file.errors.is_empty(), "{:?}", file.errors);
+        assert_eq!(file.table.len(), 9);
+        assert_eq!(next_hop(&file.table, "10.20.30.40"), Some("lab-switch"));
+        assert_eq!(next_hop(&file.table, "2001:db8:abcd::1"), Some("lab-v6"));
+        assert!(load(Path::new("no-such-routes.txt")).is_err());
+    }
+}
@@ -83,7 +83,15 @@
 // }
 
 use std::collections::HashMap;
+use std::env;
+use std::process;
 
+use crate::messages::{Lang, Message};
+
+mod messages;
+
+// is_multiple_of 要 Rust 1.87 才有，这里保留 % 2 的写法
+#[allow(clippy::manual_is_multiple_of)]
 fn find_median_and_mode(mut nums: Vec<i32>) -> (f64, i32) {
     // 计算中位数
     nums.sort();
@@ -109,8 +117,15 @@ fn find_median_and_mode(mut nums: Vec<i32>) -> (f64, i32) {
 }
 
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let lang = Lang::detect(&args);
+    if let Err(tag) = Lang::from_args(&args) {
+        eprintln!("{}", Message::UnknownLang(tag).text(lang));
+        process::exit(2);
+    }
+
     let nums = vec![3, 1, 2, 2, 5, 2];
     let (median, mode) = find_median_and_mode(nums);
-    println!("中位数: {}", median);
-    println!("众数: {}", mode);
+    println!("{}", Message::Median(median).text(lang));
+    println!("{}", Message::Mode(mode).text(lang));
 }
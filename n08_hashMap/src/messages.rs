@@ -0,0 +1,20 @@
+pub use lang::Lang;
+
+pub enum Message {
+    Median(f64),
+    Mode(i32),
+    UnknownLang(String),
+}
+
+impl Message {
+    pub fn text(&self, lang: Lang) -> String {
+        match (lang, self) {
+            (Lang::En, Message::Median(median)) => format!("Median: {median}"),
+            (Lang::En, Message::Mode(mode)) => format!("Mode: {mode}"),
+            (Lang::En, Message::UnknownLang(tag)) => format!("Unknown language '{tag}', expected en or zh."),
+            (Lang::Zh, Message::Median(median)) => format!("中位数: {median}"),
+            (Lang::Zh, Message::Mode(mode)) => format!("众数: {mode}"),
+            (Lang::Zh, Message::UnknownLang(tag)) => format!("未知语言 '{tag}'，可选 en 或 zh。"),
+        }
+    }
+}
@@ -175,21 +175,69 @@
 
 #![allow(unused)]
 fn main() {
+    use std::fmt;
+
     pub struct Guess {
         value: i32,
     }
 
+    // OutOfRange 保存原始文字，超出 i32 的一长串数字也能原样报告
+    #[derive(Debug)]
+    pub enum GuessError {
+        NotANumber(String),
+        OutOfRange(String),
+    }
+
+    impl fmt::Display for GuessError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                GuessError::NotANumber(input) => write!(f, "'{input}' is not a number."),
+                GuessError::OutOfRange(value) => {
+                    write!(f, "Guess value must be between 1 and 100, got {value}.")
+                }
+            }
+        }
+    }
+
     impl Guess {
         pub fn new(value: i32) -> Guess {
-            if value < 1 || value > 100 {
-                panic!("Guess value must be between 1 and 100, got {value}.");
+            match Guess::try_new(value) {
+                Ok(guess) => guess,
+                Err(error) => panic!("{error}"),
+            }
+        }
+
+        // 不想 panic 时使用，把错误交给调用者处理
+        pub fn try_new(value: i32) -> Result<Guess, GuessError> {
+            if !(1..=100).contains(&value) {
+                return Err(GuessError::OutOfRange(value.to_string()));
             }
 
-            Guess { value }
+            Ok(Guess { value })
+        }
+
+        pub fn parse(input: &str) -> Result<Guess, GuessError> {
+            let input = input.trim();
+            let digits = input.strip_prefix(['-', '+']).unwrap_or(input);
+            match input.parse() {
+                Ok(value) => Guess::try_new(value),
+                // 纯数字但超出 i32 的输入也算超出范围
+                Err(_) if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) => {
+                    Err(GuessError::OutOfRange(input.to_string()))
+                }
+                Err(_) => Err(GuessError::NotANumber(input.to_string())),
+            }
         }
 
         pub fn value(&self) -> i32 {
             self.value
         }
     }
-}
\ No newline at end of file
+
+    for input in ["42", "0", "500", "abc", "99999999999999999999"] {
+        match Guess::parse(input) {
+            Ok(guess) => println!("guess: {}", guess.value()),
+            Err(error) => println!("error: {error}"),
+        }
+    }
+}
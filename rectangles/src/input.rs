@@ -0,0 +1,70 @@
+use std::fmt;
+use std::io::BufRead;
+
+use crate::Rectangle;
+
+// 每行一个矩形，格式为 width,height；空行和 # 开头的注释行会被忽略
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    FieldCount(usize),
+    InvalidNumber { field: &'static str, value: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::FieldCount(count) => {
+                write!(f, "expected 2 fields (width,height), found {count}")
+            }
+            ParseError::InvalidNumber { field, value } => {
+                write!(f, "{field} '{value}' is not a non-negative integer")
+            }
+        }
+    }
+}
+
+pub fn parse_line(line: &str) -> Result<Rectangle, ParseError> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    if fields.len() != 2 {
+        return Err(ParseError::FieldCount(fields.len()));
+    }
+
+    let number = |field: &'static str, value: &str| {
+        value.parse().map_err(|_| ParseError::InvalidNumber { field, value: value.to_string() })
+    };
+    Ok(Rectangle {
+        width: number("width", fields[0])?,
+        height: number("height", fields[1])?,
+    })
+}
+
+// 行号从 1 开始；读取失败（例如不是 UTF-8）的行也记成错误而不是中断
+#[derive(Debug, Default)]
+pub struct Rows {
+    pub rectangles: Vec<(usize, Rectangle)>,
+    pub errors: Vec<(usize, String)>,
+}
+
+pub fn read_rows(reader: impl BufRead) -> Rows {
+    let mut rows = Rows::default();
+    for (index, line) in reader.lines().enumerate() {
+        let number = index + 1;
+        let line = match line {
+            Ok(line) => line,
+            Err(error) => {
+                rows.errors.push((number, error.to_string()));
+                continue;
+            }
+        };
+
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || (number == 1 && line.eq_ignore_ascii_case("width,height")) {
+            continue;
+        }
+        match parse_line(line) {
+            Ok(rectangle) => rows.rectangles.push((number, rectangle)),
+            Err(error) => rows.errors.push((number, error.to_string())),
+        }
+    }
+    rows
+}
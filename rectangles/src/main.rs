@@ -56,19 +56,90 @@
 //     println!("rect1 is {rect1:#?}");
 // }
 
-#[derive(Debug)] #[allow(dead_code)]
+// #[derive(Debug)] #[allow(dead_code)]
+// struct Rectangle {
+//     width: u32,
+//     height: u32,
+// }
+//
+// fn main() {
+//     let scale = 2;
+//     let rect1 = Rectangle {
+//         width: dbg!(30 * scale),
+//         height: 50,
+//     };
+//
+//     dbg!(&rect1);
+// }
+
+// 从标准输入或 CSV 文件读取矩形
+use std::env;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::process;
+
+mod input;
+
+#[derive(Debug)]
 struct Rectangle {
     width: u32,
     height: u32,
 }
 
+impl Rectangle {
+    fn area(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+
+    fn can_hold(&self, other: &Rectangle) -> bool {
+        self.width > other.width && self.height > other.height
+    }
+}
+
 fn main() {
-    let scale = 2;
-    let rect1 = Rectangle {
-        width: dbg!(30 * scale),
-        height: 50,
+    let path = env::args().nth(1);
+    let rows = match path.as_deref() {
+        None | Some("-") => input::read_rows(io::stdin().lock()),
+        Some(path) => match File::open(path) {
+            Ok(file) => input::read_rows(BufReader::new(file)),
+            Err(error) => {
+                eprintln!("Could not open {path}: {error}");
+                process::exit(1);
+            }
+        },
     };
 
-    dbg!(&rect1);
-}
+    for (line, rect) in &rows.rectangles {
+        println!("line {line}: {}x{} area {}", rect.width, rect.height, rect.area());
+    }
+    for (line, error) in &rows.errors {
+        eprintln!("line {line}: error: {error}");
+    }
+
+    let total: u64 = rows.rectangles.iter().map(|(_, rect)| rect.area()).sum();
+    println!(
+        "{} rectangles, {} errors, total area {total} square pixels.",
+        rows.rectangles.len(),
+        rows.errors.len()
+    );
+
+    if let Some((line, rect)) = rows.rectangles.iter().max_by_key(|(_, rect)| rect.area()) {
+        println!("The largest is line {line}: {}x{} area {}", rect.width, rect.height, rect.area());
+    }
 
+    for (outer_line, outer) in &rows.rectangles {
+        let held: Vec<String> = rows
+            .rectangles
+            .iter()
+            .filter(|(_, inner)| outer.can_hold(inner))
+            .map(|(line, _)| format!("line {line}"))
+            .collect();
+        if !held.is_empty() {
+            println!("line {outer_line} can hold {}", held.join(", "));
+        }
+    }
+
+    if !rows.errors.is_empty() {
+        process::exit(1);
+    }
+}